@@ -1,3 +1,4 @@
+use crate::analysis::indicator::{Indicator, IndicatorValue};
 use crate::error::{AppError, Result};
 use crate::models::Kline;
 
@@ -79,6 +80,25 @@ impl BollingerCalculator {
     }
 }
 
+impl Indicator for BollingerCalculator {
+    fn compute(&self, klines: &[&Kline]) -> Result<IndicatorValue> {
+        let bands = self.calculate(klines)?;
+        Ok(IndicatorValue::Bollinger {
+            upper: bands.upper,
+            middle: bands.middle,
+            lower: bands.lower,
+        })
+    }
+
+    fn min_period(&self) -> usize {
+        self.period
+    }
+
+    fn name(&self) -> &str {
+        "bollinger"
+    }
+}
+
 pub fn check_4h_volume_condition(klines: &[&Kline]) -> (bool, f64) {
     if klines.len() < 7 {
         return (false, 0.0);
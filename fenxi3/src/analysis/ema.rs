@@ -0,0 +1,59 @@
+use crate::analysis::indicator::{Indicator, IndicatorValue};
+use crate::error::{AppError, Result};
+use crate::models::Kline;
+
+pub struct EmaCalculator {
+    period: usize,
+}
+
+impl EmaCalculator {
+    pub fn new(period: usize) -> Self {
+        Self { period }
+    }
+
+    pub fn calculate(&self, klines: &[&Kline]) -> Result<f64> {
+        if klines.len() < self.period {
+            return Err(AppError::InsufficientData {
+                required: self.period,
+                actual: klines.len(),
+            });
+        }
+
+        let closes: Vec<f64> = klines
+            .iter()
+            .skip(klines.len() - self.period)
+            .map(|k| k.close)
+            .collect();
+
+        Ok(ema_series(&closes, self.period).last().copied().unwrap())
+    }
+}
+
+impl Indicator for EmaCalculator {
+    fn compute(&self, klines: &[&Kline]) -> Result<IndicatorValue> {
+        Ok(IndicatorValue::Ema(self.calculate(klines)?))
+    }
+
+    fn min_period(&self) -> usize {
+        self.period
+    }
+
+    fn name(&self) -> &str {
+        "ema"
+    }
+}
+
+/// Seeds the EMA with `closes[0]` and smooths forward, returning one value
+/// per input close. Shared with `MacdCalculator`, which needs the full
+/// series rather than only the latest value.
+pub(crate) fn ema_series(closes: &[f64], period: usize) -> Vec<f64> {
+    let k = 2.0 / (period as f64 + 1.0);
+    let mut out = Vec::with_capacity(closes.len());
+    let mut ema = closes[0];
+    out.push(ema);
+    for &close in &closes[1..] {
+        ema = close * k + ema * (1.0 - k);
+        out.push(ema);
+    }
+    out
+}
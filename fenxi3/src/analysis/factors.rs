@@ -0,0 +1,132 @@
+use crate::models::Kline;
+use serde::Deserialize;
+
+const RELATIVE_VOLUME_LOOKBACK: usize = 20;
+const MA_PERIODS: [usize; 4] = [3, 5, 10, 20];
+
+/// Per-bar volume/liquidity factors computed by `calc_all_factors`, attached
+/// to each `BuySignal` so a scanned CSV can be correlated against realized
+/// `profit_pct` downstream. A factor disabled in `FactorToggles` is left at
+/// its neutral value (`1.0`/`false`/`0.5`) rather than omitted, so the CSV
+/// schema stays stable across toggle configurations.
+#[derive(Debug, Clone, Copy)]
+pub struct VolumeFactors {
+    /// Current bar's volume divided by the trailing
+    /// `RELATIVE_VOLUME_LOOKBACK`-bar average of prior volume.
+    pub relative_volume_ratio: f64,
+    /// Whether MA3 > MA5 > MA10 > MA20 of close price, i.e. short-term
+    /// momentum is stacked above longer-term momentum.
+    pub ma_stack_aligned: bool,
+    /// `taker_buy_volume / volume` for the bar: how much of the traded
+    /// volume was taker-initiated buying versus selling.
+    pub taker_buy_ratio: f64,
+}
+
+impl VolumeFactors {
+    fn neutral() -> Self {
+        Self {
+            relative_volume_ratio: 1.0,
+            ma_stack_aligned: false,
+            taker_buy_ratio: 0.5,
+        }
+    }
+}
+
+/// Individually enables/disables each factor `calc_all_factors` computes, so
+/// a strategy can opt out of a factor that doesn't fit a given market
+/// without losing the others.
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct FactorToggles {
+    #[serde(default = "default_true")]
+    pub relative_volume: bool,
+    #[serde(default = "default_true")]
+    pub ma_stack: bool,
+    #[serde(default = "default_true")]
+    pub taker_buy_pressure: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for FactorToggles {
+    fn default() -> Self {
+        Self {
+            relative_volume: true,
+            ma_stack: true,
+            taker_buy_pressure: true,
+        }
+    }
+}
+
+/// Computes `VolumeFactors` for every bar in `klines`, reusable across
+/// `FastScanner::scan_klines` and `FastScanner::scan_symbol_trades` as an
+/// additional set of values to correlate with signal quality. Each enabled
+/// factor is computed with a sliding window, the same way
+/// `FastScanner::calc_all_boll` slides its sum across the window rather than
+/// re-summing every bar.
+pub fn calc_all_factors(klines: &[Kline], toggles: &FactorToggles) -> Vec<VolumeFactors> {
+    let mut out = vec![VolumeFactors::neutral(); klines.len()];
+
+    if toggles.relative_volume {
+        calc_relative_volume(klines, &mut out);
+    }
+    if toggles.ma_stack {
+        calc_ma_stack(klines, &mut out);
+    }
+    if toggles.taker_buy_pressure {
+        for (i, k) in klines.iter().enumerate() {
+            out[i].taker_buy_ratio = if k.volume > 0.0 {
+                k.taker_buy_volume / k.volume
+            } else {
+                0.5
+            };
+        }
+    }
+
+    out
+}
+
+fn calc_relative_volume(klines: &[Kline], out: &mut [VolumeFactors]) {
+    let mut sum = 0.0;
+    for i in 0..klines.len() {
+        let window_len = i - i.saturating_sub(RELATIVE_VOLUME_LOOKBACK);
+        if window_len > 0 {
+            let avg = sum / window_len as f64;
+            if avg > 0.0 {
+                out[i].relative_volume_ratio = klines[i].volume / avg;
+            }
+        }
+
+        sum += klines[i].volume;
+        if i >= RELATIVE_VOLUME_LOOKBACK {
+            sum -= klines[i - RELATIVE_VOLUME_LOOKBACK].volume;
+        }
+    }
+}
+
+fn calc_ma_stack(klines: &[Kline], out: &mut [VolumeFactors]) {
+    let mut sums = [0.0; MA_PERIODS.len()];
+    for i in 0..klines.len() {
+        for (sum, &period) in sums.iter_mut().zip(MA_PERIODS.iter()) {
+            *sum += klines[i].close;
+            if i >= period {
+                *sum -= klines[i - period].close;
+            }
+        }
+
+        let longest = MA_PERIODS[MA_PERIODS.len() - 1];
+        if i + 1 < longest {
+            continue;
+        }
+
+        let mas = sums
+            .iter()
+            .zip(MA_PERIODS.iter())
+            .map(|(&sum, &period)| sum / period as f64);
+        out[i].ma_stack_aligned = mas
+            .collect::<Vec<f64>>()
+            .windows(2)
+            .all(|w| w[0] > w[1]);
+    }
+}
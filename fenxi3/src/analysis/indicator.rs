@@ -0,0 +1,29 @@
+use crate::error::Result;
+use crate::models::Kline;
+
+/// A computed indicator reading, general enough that the scanner doesn't
+/// need to know which concrete indicator produced it.
+#[derive(Debug, Clone)]
+pub enum IndicatorValue {
+    Bollinger { upper: f64, middle: f64, lower: f64 },
+    Ema(f64),
+    Rsi(f64),
+    Macd { macd: f64, signal: f64, histogram: f64 },
+    Kdj { k: f64, d: f64, j: f64 },
+}
+
+/// A technical indicator computable over a trailing window of klines.
+///
+/// Implementations are assembled into a `Vec<Box<dyn Indicator>>` so the
+/// scanner can evaluate whichever set a user configures without any of its
+/// scan logic being aware of the concrete indicators in play.
+pub trait Indicator: Send + Sync {
+    /// Computes the indicator's latest value from `klines` (oldest first).
+    fn compute(&self, klines: &[&Kline]) -> Result<IndicatorValue>;
+
+    /// Minimum number of klines required before `compute` can succeed.
+    fn min_period(&self) -> usize;
+
+    /// Short label used to attribute a reading back to this indicator.
+    fn name(&self) -> &str;
+}
@@ -0,0 +1,101 @@
+use crate::analysis::indicator::{Indicator, IndicatorValue};
+use crate::error::{AppError, Result};
+use crate::models::Kline;
+
+#[derive(Debug, Clone, Copy)]
+pub struct KdjValue {
+    pub k: f64,
+    pub d: f64,
+    pub j: f64,
+}
+
+/// KDJ stochastic oscillator over a trailing `period`-bar window of
+/// high/low/close, smoothed the classic way: K = 2/3·K_prev + 1/3·RSV,
+/// D = 2/3·D_prev + 1/3·K, J = 3K - 2D.
+pub struct KdjCalculator {
+    period: usize,
+}
+
+impl KdjCalculator {
+    pub fn new(period: usize) -> Self {
+        Self { period }
+    }
+
+    pub fn calculate(&self, klines: &[&Kline]) -> Result<KdjValue> {
+        if klines.len() < self.period {
+            return Err(AppError::InsufficientData {
+                required: self.period,
+                actual: klines.len(),
+            });
+        }
+
+        let hlc: Vec<(f64, f64, f64)> = klines.iter().map(|k| (k.high, k.low, k.close)).collect();
+        kdj_series(&hlc, self.period)
+            .pop()
+            .ok_or(AppError::InsufficientData {
+                required: self.period,
+                actual: klines.len(),
+            })
+    }
+}
+
+impl Indicator for KdjCalculator {
+    fn compute(&self, klines: &[&Kline]) -> Result<IndicatorValue> {
+        let kdj = self.calculate(klines)?;
+        Ok(IndicatorValue::Kdj {
+            k: kdj.k,
+            d: kdj.d,
+            j: kdj.j,
+        })
+    }
+
+    fn min_period(&self) -> usize {
+        self.period
+    }
+
+    fn name(&self) -> &str {
+        "kdj"
+    }
+}
+
+/// Computes one `KdjValue` per bar from index `period - 1` onward, same
+/// indexing convention as `scanner::calc_all_boll`'s `BollValue` series.
+/// `K`/`D` are seeded at 50 on the first valid bar and recurse from there,
+/// so (as with Wilder's RSI elsewhere in this module) the first few values
+/// are approximate until the smoothing converges.
+pub fn kdj_series(hlc: &[(f64, f64, f64)], period: usize) -> Vec<KdjValue> {
+    if hlc.len() < period {
+        return Vec::new();
+    }
+
+    let mut results = Vec::with_capacity(hlc.len() - period + 1);
+    let mut prev_k = 50.0;
+    let mut prev_d = 50.0;
+    let mut prev_rsv = 50.0;
+
+    for i in (period - 1)..hlc.len() {
+        let window = &hlc[i - period + 1..=i];
+        let highest_high = window.iter().map(|(h, _, _)| *h).fold(f64::MIN, f64::max);
+        let lowest_low = window.iter().map(|(_, l, _)| *l).fold(f64::MAX, f64::min);
+        let close = hlc[i].2;
+
+        let range = highest_high - lowest_low;
+        let rsv = if range > 0.0 {
+            (close - lowest_low) / range * 100.0
+        } else {
+            prev_rsv
+        };
+
+        let k = 2.0 / 3.0 * prev_k + 1.0 / 3.0 * rsv;
+        let d = 2.0 / 3.0 * prev_d + 1.0 / 3.0 * k;
+        let j = 3.0 * k - 2.0 * d;
+
+        results.push(KdjValue { k, d, j });
+
+        prev_k = k;
+        prev_d = d;
+        prev_rsv = rsv;
+    }
+
+    results
+}
@@ -0,0 +1,65 @@
+use crate::analysis::ema::ema_series;
+use crate::analysis::indicator::{Indicator, IndicatorValue};
+use crate::error::{AppError, Result};
+use crate::models::Kline;
+
+pub struct MacdCalculator {
+    fast_period: usize,
+    slow_period: usize,
+    signal_period: usize,
+}
+
+impl MacdCalculator {
+    pub fn new(fast_period: usize, slow_period: usize, signal_period: usize) -> Self {
+        Self {
+            fast_period,
+            slow_period,
+            signal_period,
+        }
+    }
+
+    /// Returns `(macd, signal, histogram)` for the latest close.
+    pub fn calculate(&self, klines: &[&Kline]) -> Result<(f64, f64, f64)> {
+        let required = self.slow_period + self.signal_period;
+        if klines.len() < required {
+            return Err(AppError::InsufficientData {
+                required,
+                actual: klines.len(),
+            });
+        }
+
+        let closes: Vec<f64> = klines.iter().map(|k| k.close).collect();
+        let fast_ema = ema_series(&closes, self.fast_period);
+        let slow_ema = ema_series(&closes, self.slow_period);
+
+        let macd_line: Vec<f64> = fast_ema
+            .iter()
+            .zip(slow_ema.iter())
+            .map(|(fast, slow)| fast - slow)
+            .collect();
+        let signal_line = ema_series(&macd_line, self.signal_period);
+
+        let macd = *macd_line.last().unwrap();
+        let signal = *signal_line.last().unwrap();
+        Ok((macd, signal, macd - signal))
+    }
+}
+
+impl Indicator for MacdCalculator {
+    fn compute(&self, klines: &[&Kline]) -> Result<IndicatorValue> {
+        let (macd, signal, histogram) = self.calculate(klines)?;
+        Ok(IndicatorValue::Macd {
+            macd,
+            signal,
+            histogram,
+        })
+    }
+
+    fn min_period(&self) -> usize {
+        self.slow_period + self.signal_period
+    }
+
+    fn name(&self) -> &str {
+        "macd"
+    }
+}
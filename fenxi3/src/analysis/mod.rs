@@ -0,0 +1,21 @@
+pub mod bollinger;
+mod ema;
+mod factors;
+pub mod indicator;
+mod kdj;
+mod macd;
+mod resample;
+mod rsi;
+pub mod scanner;
+pub mod stats;
+
+pub use bollinger::{check_4h_volume_condition, BollingerBands, BollingerCalculator};
+pub use ema::EmaCalculator;
+pub use factors::{calc_all_factors, FactorToggles, VolumeFactors};
+pub use indicator::{Indicator, IndicatorValue};
+pub use kdj::{KdjCalculator, KdjValue};
+pub use macd::MacdCalculator;
+pub use resample::{resample, Resolution};
+pub use rsi::RsiCalculator;
+pub use scanner::{ExitStrategy, FastScanner, ScanParams};
+pub use stats::{print_statistics, BacktestStats};
@@ -0,0 +1,85 @@
+use crate::models::Kline;
+use std::collections::BTreeMap;
+
+/// Target timeframe `resample` aggregates a finer base interval into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    Minutes30,
+    Hours4,
+}
+
+impl Resolution {
+    fn as_millis(self) -> i64 {
+        match self {
+            Resolution::Minutes30 => 30 * 60 * 1000,
+            Resolution::Hours4 => 4 * 60 * 60 * 1000,
+        }
+    }
+}
+
+/// Aggregates `base` (assumed chronologically sorted, fixed-interval
+/// candles) into `target`-sized candles: bucket each base kline by
+/// `floor(open_time / target_ms) * target_ms`, then open_time/open taken
+/// from the bucket's first member, close_time/close from its last, and
+/// high/low/volume as max/min/sum across the bucket. The leading and
+/// trailing buckets are dropped if they hold fewer base klines than a full
+/// bucket would, so a partial first/last candle (the feed didn't start or
+/// end exactly on a bucket boundary) never feeds into signal generation.
+pub fn resample(base: &[Kline], target: Resolution) -> Vec<Kline> {
+    if base.is_empty() {
+        return Vec::new();
+    }
+
+    let target_ms = target.as_millis();
+    let base_ms = if base.len() > 1 {
+        (base[1].open_time - base[0].open_time).max(1)
+    } else {
+        target_ms
+    };
+    let expected_per_bucket = (target_ms / base_ms).max(1) as usize;
+
+    let mut buckets: BTreeMap<i64, Vec<&Kline>> = BTreeMap::new();
+    for k in base {
+        let bucket_start = (k.open_time / target_ms) * target_ms;
+        buckets.entry(bucket_start).or_default().push(k);
+    }
+
+    let bucket_starts: Vec<i64> = buckets.keys().copied().collect();
+    let mut result = Vec::with_capacity(bucket_starts.len());
+
+    for (i, bucket_start) in bucket_starts.iter().enumerate() {
+        let members = &buckets[bucket_start];
+        let is_edge_bucket = i == 0 || i == bucket_starts.len() - 1;
+        if is_edge_bucket && members.len() < expected_per_bucket {
+            continue;
+        }
+
+        let open = members.first().unwrap().open;
+        let last = members.last().unwrap();
+        let close = last.close;
+        let high = members.iter().map(|k| k.high).fold(f64::MIN, f64::max);
+        let low = members.iter().map(|k| k.low).fold(f64::MAX, f64::min);
+        let volume: f64 = members.iter().map(|k| k.volume).sum();
+        let quote_volume: f64 = members.iter().map(|k| k.quote_volume).sum();
+        let count: i64 = members.iter().map(|k| k.count).sum();
+        let taker_buy_volume: f64 = members.iter().map(|k| k.taker_buy_volume).sum();
+        let taker_buy_quote_volume: f64 = members.iter().map(|k| k.taker_buy_quote_volume).sum();
+
+        result.push(Kline {
+            open_time: members.first().unwrap().open_time,
+            open,
+            high,
+            low,
+            close,
+            volume,
+            close_time: last.close_time,
+            quote_volume,
+            count,
+            taker_buy_volume,
+            taker_buy_quote_volume,
+            ignore: 0,
+        });
+    }
+
+    result
+}
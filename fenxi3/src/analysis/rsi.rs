@@ -0,0 +1,61 @@
+use crate::analysis::indicator::{Indicator, IndicatorValue};
+use crate::error::{AppError, Result};
+use crate::models::Kline;
+
+/// Wilder's-smoothed RSI over a trailing window of closes.
+pub struct RsiCalculator {
+    period: usize,
+}
+
+impl RsiCalculator {
+    pub fn new(period: usize) -> Self {
+        Self { period }
+    }
+
+    pub fn calculate(&self, klines: &[&Kline]) -> Result<f64> {
+        if klines.len() < self.period + 1 {
+            return Err(AppError::InsufficientData {
+                required: self.period + 1,
+                actual: klines.len(),
+            });
+        }
+
+        // Wilder's smoothing: seed avg_gain/avg_loss from the simple mean of
+        // the first `period` diffs, then recursively fold in every later
+        // diff in the available window via avg = (avg*(period-1)+x)/period,
+        // same as the classic RSI recurrence.
+        let diffs: Vec<f64> = klines.windows(2).map(|p| p[1].close - p[0].close).collect();
+        let (seed, rest) = diffs.split_at(self.period);
+
+        let mut avg_gain = seed.iter().filter(|d| **d >= 0.0).sum::<f64>() / self.period as f64;
+        let mut avg_loss = seed.iter().filter(|d| **d < 0.0).map(|d| -*d).sum::<f64>() / self.period as f64;
+
+        let period_minus_one = (self.period - 1) as f64;
+        for diff in rest {
+            let (gain, loss) = if *diff >= 0.0 { (*diff, 0.0) } else { (0.0, -diff) };
+            avg_gain = (avg_gain * period_minus_one + gain) / self.period as f64;
+            avg_loss = (avg_loss * period_minus_one + loss) / self.period as f64;
+        }
+
+        if avg_loss == 0.0 {
+            return Ok(100.0);
+        }
+
+        let rs = avg_gain / avg_loss;
+        Ok(100.0 - 100.0 / (1.0 + rs))
+    }
+}
+
+impl Indicator for RsiCalculator {
+    fn compute(&self, klines: &[&Kline]) -> Result<IndicatorValue> {
+        Ok(IndicatorValue::Rsi(self.calculate(klines)?))
+    }
+
+    fn min_period(&self) -> usize {
+        self.period + 1
+    }
+
+    fn name(&self) -> &str {
+        "rsi"
+    }
+}
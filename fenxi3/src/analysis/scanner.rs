@@ -1,13 +1,27 @@
+use crate::analysis::factors::{calc_all_factors, FactorToggles};
+use crate::analysis::indicator::{Indicator, IndicatorValue};
+use crate::analysis::kdj::{kdj_series, KdjValue};
+use crate::analysis::resample::{resample, Resolution};
 use crate::error::Result;
-use crate::models::{BuySignal, Kline, TradeResult};
+use crate::loader::KlineCache;
+use crate::models::{BuySignal, Kline, Metrics, TradeResult};
+use serde::Deserialize;
 use std::fs::{self, File};
 use std::path::Path;
+use tracing::{debug, warn};
 
 const BOLL_PERIOD: usize = 400;
 const BOLL_STD_DEV: f64 = 2.0;
 const HISTORY_CHECK_COUNT: usize = 50;
 const HISTORY_THRESHOLD: usize = 25;
+const OI_MULTIPLIER: f64 = 0.9;
 const COOLDOWN_MS: i64 = 2 * 24 * 60 * 60 * 1000; // 2天冷却期
+const THREE_DAYS_MS: i64 = 3 * 24 * 60 * 60 * 1000;
+const KDJ_PERIOD: usize = 9;
+const KDJ_OVERSOLD_THRESHOLD: f64 = 20.0;
+const TAKE_PROFIT_PCT: f64 = 10.0;
+const STOP_LOSS_PCT: f64 = 5.0;
+const VOLUME_RATIO_MULTIPLIER: f64 = 2.0;
 
 #[derive(Debug, Clone)]
 struct BollValue {
@@ -15,11 +29,220 @@ struct BollValue {
     middle: f64,
 }
 
-pub struct FastScanner;
+/// Why a trade opened by `scan_symbol_trades` was closed. Checked in this
+/// priority order against every bar since entry: a hard stop-loss or
+/// take-profit should win over a slower-to-trigger trailing stop, which in
+/// turn should win over the original Bollinger band-cross exit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExitReason {
+    StopLoss,
+    TakeProfit,
+    TrailingStop,
+    BandCross,
+}
+
+impl ExitReason {
+    fn as_str(self) -> &'static str {
+        match self {
+            ExitReason::StopLoss => "stop_loss",
+            ExitReason::TakeProfit => "take_profit",
+            ExitReason::TrailingStop => "trailing_stop",
+            ExitReason::BandCross => "band_cross",
+        }
+    }
+}
+
+/// Configurable exit rules for `scan_symbol_trades`, evaluated on every bar
+/// after the buy: stop-loss/take-profit against the buy price, the trailing
+/// stop against the highest close since entry. `trailing_stop_pct`
+/// is optional: leaving it unset disables the trailing-stop check and falls
+/// back to stop-loss/take-profit/band-cross only.
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct ExitStrategy {
+    #[serde(default = "default_take_profit_pct")]
+    pub take_profit_pct: f64,
+    #[serde(default = "default_stop_loss_pct")]
+    pub stop_loss_pct: f64,
+    #[serde(default)]
+    pub trailing_stop_pct: Option<f64>,
+}
+
+fn default_take_profit_pct() -> f64 {
+    TAKE_PROFIT_PCT
+}
+
+fn default_stop_loss_pct() -> f64 {
+    STOP_LOSS_PCT
+}
+
+impl Default for ExitStrategy {
+    fn default() -> Self {
+        Self {
+            take_profit_pct: TAKE_PROFIT_PCT,
+            stop_loss_pct: STOP_LOSS_PCT,
+            trailing_stop_pct: None,
+        }
+    }
+}
+
+/// One named strategy's worth of tunable thresholds. The Bollinger,
+/// history and OI fields mirror `prophet::config::AnalysisConfig` so the
+/// same values configured for the live/polling analyzer can be replayed
+/// here; the remaining fields are scanner-only. Several
+/// of these can be scanned over the same loaded klines in one pass (see
+/// `FastScanner::new`), each producing signals tagged with its `name`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScanParams {
+    pub name: String,
+    #[serde(default = "default_boll_period")]
+    pub boll_period: usize,
+    #[serde(default = "default_boll_std_dev")]
+    pub boll_std_dev: f64,
+    #[serde(default = "default_history_check_count")]
+    pub history_check_count: usize,
+    #[serde(default = "default_history_threshold")]
+    pub history_threshold: usize,
+    #[serde(default = "default_oi_multiplier")]
+    pub oi_multiplier: f64,
+    #[serde(default = "default_cooldown_ms")]
+    pub cooldown_ms: i64,
+    /// Lookback window for the KDJ stochastic oscillator.
+    #[serde(default = "default_kdj_period")]
+    pub kdj_period: usize,
+    /// J must be below this on the entry bar (oversold), gating the
+    /// Bollinger breakout so it doesn't fire on an already-stretched move.
+    #[serde(default = "default_kdj_oversold_threshold")]
+    pub kdj_oversold_threshold: f64,
+    /// Take-profit/stop-loss/trailing-stop rules applied by
+    /// `scan_symbol_trades` on top of the original band-cross exit.
+    #[serde(default)]
+    pub exit: ExitStrategy,
+    /// The latest 4h bar's volume times this multiplier must exceed the sum
+    /// of the preceding 6 bars for `check_4h_volume` to pass.
+    #[serde(default = "default_volume_ratio_multiplier")]
+    pub volume_ratio_multiplier: f64,
+    /// Which of `calc_all_factors`'s relative-volume/MA-stack/taker-buy
+    /// factors to compute and attach to each `BuySignal`.
+    #[serde(default)]
+    pub factors: FactorToggles,
+}
+
+fn default_boll_period() -> usize {
+    BOLL_PERIOD
+}
+
+fn default_boll_std_dev() -> f64 {
+    BOLL_STD_DEV
+}
+
+fn default_history_check_count() -> usize {
+    HISTORY_CHECK_COUNT
+}
+
+fn default_history_threshold() -> usize {
+    HISTORY_THRESHOLD
+}
+
+fn default_oi_multiplier() -> f64 {
+    OI_MULTIPLIER
+}
+
+fn default_cooldown_ms() -> i64 {
+    COOLDOWN_MS
+}
+
+fn default_kdj_period() -> usize {
+    KDJ_PERIOD
+}
+
+fn default_kdj_oversold_threshold() -> f64 {
+    KDJ_OVERSOLD_THRESHOLD
+}
+
+fn default_volume_ratio_multiplier() -> f64 {
+    VOLUME_RATIO_MULTIPLIER
+}
+
+impl Default for ScanParams {
+    fn default() -> Self {
+        Self {
+            name: "default".to_string(),
+            boll_period: BOLL_PERIOD,
+            boll_std_dev: BOLL_STD_DEV,
+            history_check_count: HISTORY_CHECK_COUNT,
+            history_threshold: HISTORY_THRESHOLD,
+            oi_multiplier: OI_MULTIPLIER,
+            cooldown_ms: COOLDOWN_MS,
+            kdj_period: KDJ_PERIOD,
+            kdj_oversold_threshold: KDJ_OVERSOLD_THRESHOLD,
+            exit: ExitStrategy::default(),
+            volume_ratio_multiplier: VOLUME_RATIO_MULTIPLIER,
+            factors: FactorToggles::default(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct FastScanner {
+    strategies: Vec<ScanParams>,
+    indicators: Vec<Box<dyn Indicator>>,
+}
 
 impl FastScanner {
-    pub fn new() -> Self {
-        Self
+    /// Scans with one strategy per entry in `strategies`; an empty list
+    /// falls back to `ScanParams::default()` so existing single-strategy
+    /// callers don't need to change.
+    pub fn new(strategies: Vec<ScanParams>) -> Self {
+        let strategies = if strategies.is_empty() {
+            vec![ScanParams::default()]
+        } else {
+            strategies
+        };
+        Self {
+            strategies,
+            indicators: Vec::new(),
+        }
+    }
+
+    /// Builds a scanner that also evaluates `indicators` alongside the
+    /// hard-wired Bollinger/volume/OI condition chain for each strategy.
+    /// An indicator without `min_period()` klines of history yet is skipped;
+    /// a failure to compute one is logged and skipped rather than aborting
+    /// the scan.
+    pub fn with_indicators(
+        strategies: Vec<ScanParams>,
+        indicators: Vec<Box<dyn Indicator>>,
+    ) -> Self {
+        let mut scanner = Self::new(strategies);
+        scanner.indicators = indicators;
+        scanner
+    }
+
+    /// Evaluates the configured extra indicators against the trailing
+    /// window ending at `klines[up_to]` and logs their values at debug
+    /// level. This does not affect signal generation; it exists so
+    /// additional indicators can be wired into a `BuySignal` without
+    /// touching the core scan loop above.
+    fn evaluate_extra_indicators(&self, symbol: &str, klines: &[Kline], up_to: usize) {
+        if self.indicators.is_empty() {
+            return;
+        }
+
+        let window: Vec<&Kline> = klines[..=up_to].iter().collect();
+        for indicator in &self.indicators {
+            if window.len() < indicator.min_period() {
+                continue;
+            }
+            match indicator.compute(&window) {
+                Ok(value) => debug!(
+                    symbol,
+                    indicator = indicator.name(),
+                    ?value,
+                    "indicator computed"
+                ),
+                Err(err) => debug!(symbol, indicator = indicator.name(), %err, "indicator skipped"),
+            }
+        }
     }
 
     pub fn scan_symbol(&self, data_path: &Path, symbol: &str) -> Result<Vec<BuySignal>> {
@@ -27,18 +250,64 @@ impl FastScanner {
         let klines_15m = Self::load_all_klines(data_path, symbol, "15m")?;
         let klines_30m = Self::load_all_klines(data_path, symbol, "30m")?;
         let klines_4h = Self::load_all_klines(data_path, symbol, "4h")?;
+        let metrics = Self::load_all_metrics(data_path, symbol)?;
 
-        if klines_15m.len() < BOLL_PERIOD
-            || klines_30m.len() < BOLL_PERIOD
-            || klines_4h.len() < BOLL_PERIOD
-        {
-            return Ok(Vec::new());
+        Ok(self.scan_all_strategies(symbol, &klines_15m, &klines_30m, &klines_4h, &metrics))
+    }
+
+    /// Same scan as `scan_symbol`, but only reads the 15m CSV feed from
+    /// disk and synthesizes 30m/4h candles from it via `resample`. This
+    /// guarantees all three timeframes come from one underlying feed
+    /// (no cross-feed misalignment) and halves on-disk storage.
+    pub fn scan_symbol_resampled(&self, data_path: &Path, symbol: &str) -> Result<Vec<BuySignal>> {
+        let klines_15m = Self::load_all_klines(data_path, symbol, "15m")?;
+        let klines_30m = resample(&klines_15m, Resolution::Minutes30);
+        let klines_4h = resample(&klines_15m, Resolution::Hours4);
+        let metrics = Self::load_all_metrics(data_path, symbol)?;
+
+        Ok(self.scan_all_strategies(symbol, &klines_15m, &klines_30m, &klines_4h, &metrics))
+    }
+
+    /// Runs every configured `ScanParams` over the same already-loaded
+    /// klines/metrics, so comparing strategy variants doesn't mean
+    /// re-reading CSVs once per variant.
+    fn scan_all_strategies(
+        &self,
+        symbol: &str,
+        klines_15m: &[Kline],
+        klines_30m: &[Kline],
+        klines_4h: &[Kline],
+        metrics: &[Metrics],
+    ) -> Vec<BuySignal> {
+        self.strategies
+            .iter()
+            .flat_map(|params| {
+                self.scan_klines(symbol, klines_15m, klines_30m, klines_4h, metrics, params)
+            })
+            .collect()
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn scan_klines(
+        &self,
+        symbol: &str,
+        klines_15m: &[Kline],
+        klines_30m: &[Kline],
+        klines_4h: &[Kline],
+        metrics: &[Metrics],
+        params: &ScanParams,
+    ) -> Vec<BuySignal> {
+        let period = params.boll_period;
+        if klines_15m.len() < period || klines_30m.len() < period || klines_4h.len() < period {
+            return Vec::new();
         }
 
         // 预计算布林带
-        let boll_15m = Self::calc_all_boll(&klines_15m);
-        let boll_30m = Self::calc_all_boll(&klines_30m);
-        let boll_4h = Self::calc_all_boll(&klines_4h);
+        let boll_15m = Self::calc_all_boll(klines_15m, params);
+        let boll_30m = Self::calc_all_boll(klines_30m, params);
+        let boll_4h = Self::calc_all_boll(klines_4h, params);
+        let kdj_15m = Self::calc_all_kdj(klines_15m, params);
+        let factors_15m = calc_all_factors(klines_15m, &params.factors);
 
         let mut signals = Vec::new();
 
@@ -47,29 +316,27 @@ impl FastScanner {
         let mut idx_4h = 0usize;
         let mut last_signal_time: Option<i64> = None; // 上次信号时间，用于冷却期
 
-        for i in (BOLL_PERIOD - 1)..klines_15m.len() {
+        for i in (period - 1)..klines_15m.len() {
             let k15 = &klines_15m[i];
             let timestamp = k15.close_time;
             let price = k15.close;
 
-            // 冷却期检查：2天内不再检测
+            // 冷却期检查：该策略的冷却时间内不再检测
             if let Some(last_time) = last_signal_time {
-                if timestamp - last_time < COOLDOWN_MS {
+                if timestamp - last_time < params.cooldown_ms {
                     continue;
                 }
             }
 
             // 同步30m索引
             while idx_30m + 1 < boll_30m.len()
-                && klines_30m[idx_30m + BOLL_PERIOD].close_time <= timestamp
+                && klines_30m[idx_30m + period].close_time <= timestamp
             {
                 idx_30m += 1;
             }
 
             // 同步4h索引
-            while idx_4h + 1 < boll_4h.len()
-                && klines_4h[idx_4h + BOLL_PERIOD].close_time <= timestamp
-            {
+            while idx_4h + 1 < boll_4h.len() && klines_4h[idx_4h + period].close_time <= timestamp {
                 idx_4h += 1;
             }
 
@@ -77,7 +344,7 @@ impl FastScanner {
                 continue;
             }
 
-            let b15 = &boll_15m[i - BOLL_PERIOD + 1];
+            let b15 = &boll_15m[i - period + 1];
             let b30 = &boll_30m[idx_30m];
             let b4h = &boll_4h[idx_4h];
 
@@ -90,56 +357,75 @@ impl FastScanner {
                 continue;
             }
 
-            // 条件4: 15m最近50根中25根以上 < 上轨
-            let start_4 = if i >= HISTORY_CHECK_COUNT { i - HISTORY_CHECK_COUNT + 1 } else { 0 };
-            let count_below_upper = klines_15m[start_4..=i]
-                .iter()
-                .filter(|k| k.close < b15.upper)
-                .count();
-            let cond4 = count_below_upper >= HISTORY_THRESHOLD;
-
+            // 条件4: 15m最近N根中M根以上 < 上轨
+            let cond4 = Self::check_history_condition(klines_15m, i, b15.upper, params);
             if !cond4 {
                 continue;
             }
 
             // 条件5: 4h成交量
-            let (cond7, volume_ratio) = Self::check_4h_volume(&klines_4h, idx_4h + BOLL_PERIOD);
+            let (cond5, volume_ratio) =
+                Self::check_4h_volume(klines_4h, idx_4h + period, params.volume_ratio_multiplier);
+            if !cond5 {
+                continue;
+            }
 
-            if !cond7 {
+            // 条件6: 持仓量乘以 oi_multiplier 后高于3天最低点 (没有持仓量数据时视为通过,
+            // 这样没有 metrics 归档的历史回测仍然可以评估纯K线条件)
+            if !Self::check_oi_condition(metrics, timestamp, params) {
                 continue;
             }
 
+            // 条件7: 入场K线KDJ超卖 (J < kdj_oversold_threshold)，避免在布林突破
+            // 已经走了一大段行情之后才追高进场
+            if !Self::check_kdj_condition(&kdj_15m, i, params) {
+                continue;
+            }
+            let Some(kdj) = Self::kdj_at(&kdj_15m, i, params) else {
+                continue;
+            };
+
+            self.evaluate_extra_indicators(symbol, klines_15m, i);
+
+            let factors = &factors_15m[i];
             signals.push(BuySignal::new(
                 timestamp,
                 symbol.to_string(),
+                params.name.clone(),
                 price,
                 b15.upper,
                 b30.middle,
                 b4h.middle,
                 volume_ratio,
+                factors.relative_volume_ratio,
+                factors.ma_stack_aligned,
+                factors.taker_buy_ratio,
+                kdj.k,
+                kdj.d,
+                kdj.j,
             ));
-            last_signal_time = Some(timestamp); // 记录信号时间，开始2天冷却
+            last_signal_time = Some(timestamp); // 记录信号时间，开始冷却
         }
 
-        Ok(signals)
+        signals
     }
 
-    /// 扫描交易对并返回完整的交易记录（含卖出点）
+    /// 扫描交易对并返回完整的交易记录（含卖出点），使用第一个配置的策略
     pub fn scan_symbol_trades(&self, data_path: &Path, symbol: &str) -> Result<Vec<TradeResult>> {
         let klines_15m = Self::load_all_klines(data_path, symbol, "15m")?;
         let klines_30m = Self::load_all_klines(data_path, symbol, "30m")?;
         let klines_4h = Self::load_all_klines(data_path, symbol, "4h")?;
 
-        if klines_15m.len() < BOLL_PERIOD
-            || klines_30m.len() < BOLL_PERIOD
-            || klines_4h.len() < BOLL_PERIOD
-        {
+        let params = self.strategies.first().cloned().unwrap_or_default();
+        let period = params.boll_period;
+        if klines_15m.len() < period || klines_30m.len() < period || klines_4h.len() < period {
             return Ok(Vec::new());
         }
 
-        let boll_15m = Self::calc_all_boll(&klines_15m);
-        let boll_30m = Self::calc_all_boll(&klines_30m);
-        let boll_4h = Self::calc_all_boll(&klines_4h);
+        let boll_15m = Self::calc_all_boll(&klines_15m, &params);
+        let boll_30m = Self::calc_all_boll(&klines_30m, &params);
+        let boll_4h = Self::calc_all_boll(&klines_4h, &params);
+        let kdj_15m = Self::calc_all_kdj(&klines_15m, &params);
 
         let mut trades = Vec::new();
 
@@ -147,29 +433,27 @@ impl FastScanner {
         let mut idx_4h = 0usize;
         let mut last_signal_time: Option<i64> = None;
 
-        for i in (BOLL_PERIOD - 1)..klines_15m.len() {
+        for i in (period - 1)..klines_15m.len() {
             let k15 = &klines_15m[i];
             let timestamp = k15.close_time;
             let price = k15.close;
 
             // 冷却期检查
             if let Some(last_time) = last_signal_time {
-                if timestamp - last_time < COOLDOWN_MS {
+                if timestamp - last_time < params.cooldown_ms {
                     continue;
                 }
             }
 
             // 同步30m索引
             while idx_30m + 1 < boll_30m.len()
-                && klines_30m[idx_30m + BOLL_PERIOD].close_time <= timestamp
+                && klines_30m[idx_30m + period].close_time <= timestamp
             {
                 idx_30m += 1;
             }
 
             // 同步4h索引
-            while idx_4h + 1 < boll_4h.len()
-                && klines_4h[idx_4h + BOLL_PERIOD].close_time <= timestamp
-            {
+            while idx_4h + 1 < boll_4h.len() && klines_4h[idx_4h + period].close_time <= timestamp {
                 idx_4h += 1;
             }
 
@@ -177,7 +461,7 @@ impl FastScanner {
                 continue;
             }
 
-            let b15 = &boll_15m[i - BOLL_PERIOD + 1];
+            let b15 = &boll_15m[i - period + 1];
             let b30 = &boll_30m[idx_30m];
             let b4h = &boll_4h[idx_4h];
 
@@ -187,18 +471,19 @@ impl FastScanner {
             }
 
             // 条件4
-            let start_4 = if i >= HISTORY_CHECK_COUNT { i - HISTORY_CHECK_COUNT + 1 } else { 0 };
-            let count_below_upper = klines_15m[start_4..=i]
-                .iter()
-                .filter(|k| k.close < b15.upper)
-                .count();
-            if count_below_upper < HISTORY_THRESHOLD {
+            if !Self::check_history_condition(&klines_15m, i, b15.upper, &params) {
                 continue;
             }
 
             // 条件5: 4h成交量
-            let (cond7, _) = Self::check_4h_volume(&klines_4h, idx_4h + BOLL_PERIOD);
-            if !cond7 {
+            let (cond5, _) =
+                Self::check_4h_volume(&klines_4h, idx_4h + period, params.volume_ratio_multiplier);
+            if !cond5 {
+                continue;
+            }
+
+            // 条件6: 入场K线KDJ超卖，与 scan_symbol 的 cond7 保持一致
+            if !Self::check_kdj_condition(&kdj_15m, i, &params) {
                 continue;
             }
 
@@ -207,29 +492,51 @@ impl FastScanner {
                 continue; // 没有下一根K线，跳过
             }
 
+            self.evaluate_extra_indicators(symbol, &klines_15m, i);
+
             let buy_k = &klines_15m[i + 1];
             let buy_time = buy_k.close_time;
             let buy_price = buy_k.close;
+            let mut max_close_since_entry = buy_price;
 
-            // 从买入K线的下一根开始找卖出点
+            // 从买入K线的下一根开始找卖出点：止损/止盈/移动止损优先于原有的
+            // 布林上轨回落退出
             for j in (i + 2)..klines_15m.len() {
                 let sell_k = &klines_15m[j];
-                let sell_boll_idx = j - BOLL_PERIOD + 1;
-
-                if sell_boll_idx >= boll_15m.len() {
-                    break;
-                }
-
-                let sell_boll = &boll_15m[sell_boll_idx];
+                max_close_since_entry = max_close_since_entry.max(sell_k.close);
+
+                let change_pct = (sell_k.close - buy_price) / buy_price * 100.0;
+                let drawdown_from_peak_pct =
+                    (max_close_since_entry - sell_k.close) / max_close_since_entry * 100.0;
+
+                let exit_reason = if change_pct <= -params.exit.stop_loss_pct {
+                    Some(ExitReason::StopLoss)
+                } else if change_pct >= params.exit.take_profit_pct {
+                    Some(ExitReason::TakeProfit)
+                } else if params
+                    .exit
+                    .trailing_stop_pct
+                    .map(|t| drawdown_from_peak_pct >= t)
+                    .unwrap_or(false)
+                {
+                    Some(ExitReason::TrailingStop)
+                } else {
+                    let sell_boll_idx = j - period + 1;
+                    if sell_boll_idx >= boll_15m.len() {
+                        break;
+                    }
+                    // 收盘价跌破布林上轨，卖出
+                    (sell_k.close < boll_15m[sell_boll_idx].upper).then_some(ExitReason::BandCross)
+                };
 
-                // 收盘价跌破布林上轨，卖出
-                if sell_k.close < sell_boll.upper {
+                if let Some(exit_reason) = exit_reason {
                     trades.push(TradeResult::new(
                         symbol.to_string(),
                         buy_time,
                         buy_price,
                         sell_k.close_time,
                         sell_k.close,
+                        exit_reason.as_str().to_string(),
                     ));
                     last_signal_time = Some(sell_k.close_time); // 卖出后开始冷却
                     break;
@@ -240,6 +547,9 @@ impl FastScanner {
         Ok(trades)
     }
 
+    /// Loads every archived kline CSV for `symbol`/`interval`, preferring a
+    /// binary `KlineCache` hit over re-running the csv `Deserializer` when
+    /// none of the source files changed since it was written.
     fn load_all_klines(data_path: &Path, symbol: &str, interval: &str) -> Result<Vec<Kline>> {
         let dir = data_path.join("klines").join(symbol).join(interval);
         if !dir.exists() {
@@ -253,9 +563,13 @@ impl FastScanner {
             .collect();
         files.sort();
 
+        if let Some(cached) = KlineCache::load(data_path, symbol, interval, &files) {
+            return Ok(cached);
+        }
+
         let mut all_klines = Vec::new();
-        for file in files {
-            let f = File::open(&file)?;
+        for file in &files {
+            let f = File::open(file)?;
             let mut rdr = csv::Reader::from_reader(f);
             for result in rdr.deserialize::<Kline>() {
                 if let Ok(k) = result {
@@ -265,35 +579,71 @@ impl FastScanner {
                 }
             }
         }
+
+        if let Err(e) = KlineCache::store(data_path, symbol, interval, &all_klines) {
+            warn!(symbol, interval, %e, "failed to write kline cache");
+        }
+
         Ok(all_klines)
     }
 
+    /// Loads every archived metrics CSV for `symbol` (open interest /
+    /// long-short-ratio), sorted by file name the same way `load_all_klines`
+    /// sorts the kline archives. Missing/empty metrics (no `data/metrics`
+    /// tree for this symbol) isn't an error: `check_oi_condition` treats
+    /// that as "no OI data to gate on" rather than failing the scan.
+    fn load_all_metrics(data_path: &Path, symbol: &str) -> Result<Vec<Metrics>> {
+        let dir = data_path.join("metrics").join(symbol);
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
 
-    fn calc_all_boll(klines: &[Kline]) -> Vec<BollValue> {
-        if klines.len() < BOLL_PERIOD {
+        let mut files: Vec<_> = fs::read_dir(&dir)?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.extension().map(|e| e == "csv").unwrap_or(false))
+            .collect();
+        files.sort();
+
+        let mut all_metrics = Vec::new();
+        for file in files {
+            let f = File::open(&file)?;
+            let mut rdr = csv::Reader::from_reader(f);
+            for result in rdr.deserialize::<Metrics>() {
+                if let Ok(m) = result {
+                    all_metrics.push(m);
+                }
+            }
+        }
+        Ok(all_metrics)
+    }
+
+    fn calc_all_boll(klines: &[Kline], params: &ScanParams) -> Vec<BollValue> {
+        let period = params.boll_period;
+        if klines.len() < period {
             return Vec::new();
         }
 
-        let mut results = Vec::with_capacity(klines.len() - BOLL_PERIOD + 1);
+        let mut results = Vec::with_capacity(klines.len() - period + 1);
 
         // 使用滑动窗口增量计算
-        let mut sum: f64 = klines[..BOLL_PERIOD].iter().map(|k| k.close).sum();
-        let mut sum_sq: f64 = klines[..BOLL_PERIOD].iter().map(|k| k.close * k.close).sum();
+        let mut sum: f64 = klines[..period].iter().map(|k| k.close).sum();
+        let mut sum_sq: f64 = klines[..period].iter().map(|k| k.close * k.close).sum();
 
-        for i in (BOLL_PERIOD - 1)..klines.len() {
-            if i > BOLL_PERIOD - 1 {
-                let old = klines[i - BOLL_PERIOD].close;
+        for i in (period - 1)..klines.len() {
+            if i > period - 1 {
+                let old = klines[i - period].close;
                 let new = klines[i].close;
                 sum += new - old;
                 sum_sq += new * new - old * old;
             }
 
-            let mean = sum / BOLL_PERIOD as f64;
-            let variance = (sum_sq / BOLL_PERIOD as f64) - mean * mean;
+            let mean = sum / period as f64;
+            let variance = (sum_sq / period as f64) - mean * mean;
             let std_dev = variance.max(0.0).sqrt();
 
             results.push(BollValue {
-                upper: mean + std_dev * BOLL_STD_DEV,
+                upper: mean + std_dev * params.boll_std_dev,
                 middle: mean,
             });
         }
@@ -301,7 +651,60 @@ impl FastScanner {
         results
     }
 
-    fn check_4h_volume(klines: &[Kline], current_idx: usize) -> (bool, f64) {
+    /// Computes a `KdjValue` per bar from `params.kdj_period - 1` onward,
+    /// same indexing convention as `calc_all_boll`'s `BollValue` series.
+    fn calc_all_kdj(klines: &[Kline], params: &ScanParams) -> Vec<KdjValue> {
+        let hlc: Vec<(f64, f64, f64)> = klines.iter().map(|k| (k.high, k.low, k.close)).collect();
+        kdj_series(&hlc, params.kdj_period)
+    }
+
+    /// Looks up the KDJ value for main-loop index `i` (bars are indexed from
+    /// `params.boll_period - 1`, while `kdj_15m` is indexed from
+    /// `params.kdj_period - 1`) and checks the entry bar is oversold.
+    fn check_kdj_condition(kdj_15m: &[KdjValue], i: usize, params: &ScanParams) -> bool {
+        let Some(kdj_period) = params.kdj_period.checked_sub(1) else {
+            return false;
+        };
+        if i < kdj_period {
+            return false;
+        }
+        kdj_15m
+            .get(i - kdj_period)
+            .map(|v| v.j < params.kdj_oversold_threshold)
+            .unwrap_or(false)
+    }
+
+    /// Looks up the `KdjValue` for main-loop index `i`, using the same
+    /// index conversion as `check_kdj_condition`.
+    fn kdj_at(kdj_15m: &[KdjValue], i: usize, params: &ScanParams) -> Option<KdjValue> {
+        let kdj_period = params.kdj_period.checked_sub(1)?;
+        if i < kdj_period {
+            return None;
+        }
+        kdj_15m.get(i - kdj_period).copied()
+    }
+
+    fn check_history_condition(
+        klines: &[Kline],
+        i: usize,
+        upper: f64,
+        params: &ScanParams,
+    ) -> bool {
+        let check_count = params.history_check_count;
+        let start = if i >= check_count {
+            i - check_count + 1
+        } else {
+            0
+        };
+        let count_below_upper = klines[start..=i].iter().filter(|k| k.close < upper).count();
+        count_below_upper >= params.history_threshold
+    }
+
+    fn check_4h_volume(
+        klines: &[Kline],
+        current_idx: usize,
+        volume_ratio_multiplier: f64,
+    ) -> (bool, f64) {
         if current_idx < 6 || current_idx >= klines.len() {
             return (false, 0.0);
         }
@@ -313,17 +716,50 @@ impl FastScanner {
             .sum();
 
         let ratio = if sum_6 > 0.0 {
-            latest_vol * 2.0 / sum_6
+            latest_vol * volume_ratio_multiplier / sum_6
         } else {
             0.0
         };
 
-        (latest_vol * 2.0 > sum_6, ratio)
+        (latest_vol * volume_ratio_multiplier > sum_6, ratio)
     }
-}
 
-impl Default for FastScanner {
-    fn default() -> Self {
-        Self::new()
+    /// Mirrors `cond6_oi_condition` in the live/polling analyzer: current
+    /// open interest times `oi_multiplier` must exceed its trailing-3-day
+    /// minimum. No metrics archived for this symbol at all means
+    /// there's nothing to gate on, so the condition passes by default
+    /// rather than silently excluding every symbol lacking OI data.
+    fn check_oi_condition(metrics: &[Metrics], as_of: i64, params: &ScanParams) -> bool {
+        if metrics.is_empty() {
+            return true;
+        }
+
+        let Some(current_oi) = metrics
+            .iter()
+            .rev()
+            .find(|m| m.timestamp_ms() <= as_of)
+            .map(|m| m.sum_open_interest)
+        else {
+            return true;
+        };
+
+        let start_time = as_of - THREE_DAYS_MS;
+        let min_oi_3d = metrics
+            .iter()
+            .filter(|m| {
+                let t = m.timestamp_ms();
+                t >= start_time && t <= as_of
+            })
+            .map(|m| m.sum_open_interest)
+            .fold(None, |min: Option<f64>, oi| match min {
+                None => Some(oi),
+                Some(m) if oi < m => Some(oi),
+                _ => min,
+            });
+
+        match min_oi_3d {
+            Some(min_oi) => current_oi * params.oi_multiplier > min_oi,
+            None => true,
+        }
     }
 }
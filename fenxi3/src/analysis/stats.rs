@@ -0,0 +1,186 @@
+use crate::models::TradeResult;
+use serde::Serialize;
+
+/// Risk-adjusted performance summary for a completed batch of `TradeResult`s,
+/// computed once rather than printed-and-discarded so it can also be written
+/// out as a `stats.csv`/`stats.json` alongside `trades.csv`.
+#[derive(Debug, Clone, Serialize)]
+pub struct BacktestStats {
+    pub total_trades: usize,
+    pub win_count: usize,
+    pub loss_count: usize,
+    pub win_rate_pct: f64,
+    pub avg_profit_pct: f64,
+    pub max_profit_pct: f64,
+    pub max_loss_pct: f64,
+    pub avg_hold_hours: f64,
+    /// Compounded return assuming each trade risks the same fraction of
+    /// capital, i.e. the product of `(1 + profit_pct / 100)` across trades,
+    /// minus 1, as a percentage.
+    pub cumulative_return_pct: f64,
+    /// Largest peak-to-trough decline of the compounded equity curve above.
+    pub max_drawdown_pct: f64,
+    /// Sum of winning `profit_pct` divided by the absolute sum of losing
+    /// `profit_pct`; `f64::INFINITY` when there are no losing trades.
+    pub profit_factor: f64,
+    /// Mean / stddev of per-trade `profit_pct`, annualized by assuming
+    /// `365 * 24 / avg_hold_hours` trades happen per year.
+    pub sharpe_ratio: f64,
+    pub max_consecutive_wins: usize,
+    pub max_consecutive_losses: usize,
+}
+
+impl BacktestStats {
+    /// Returns `None` for an empty trade list, same as `print_statistics`
+    /// bailing out early rather than dividing by zero.
+    pub fn compute(trades: &[TradeResult]) -> Option<Self> {
+        if trades.is_empty() {
+            return None;
+        }
+
+        let total_trades = trades.len();
+        let win_count = trades.iter().filter(|t| t.profit_pct > 0.0).count();
+        let loss_count = total_trades - win_count;
+        let win_rate_pct = win_count as f64 / total_trades as f64 * 100.0;
+
+        let total_profit: f64 = trades.iter().map(|t| t.profit_pct).sum();
+        let avg_profit_pct = total_profit / total_trades as f64;
+
+        let max_profit_pct = trades
+            .iter()
+            .map(|t| t.profit_pct)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let max_loss_pct = trades
+            .iter()
+            .map(|t| t.profit_pct)
+            .fold(f64::INFINITY, f64::min);
+        let avg_hold_hours =
+            trades.iter().map(|t| t.hold_hours).sum::<f64>() / total_trades as f64;
+
+        let (cumulative_return_pct, max_drawdown_pct) = Self::equity_curve(trades);
+        let profit_factor = Self::profit_factor(trades);
+        let sharpe_ratio = Self::sharpe_ratio(trades, avg_profit_pct, avg_hold_hours);
+        let (max_consecutive_wins, max_consecutive_losses) = Self::consecutive_streaks(trades);
+
+        Some(Self {
+            total_trades,
+            win_count,
+            loss_count,
+            win_rate_pct,
+            avg_profit_pct,
+            max_profit_pct,
+            max_loss_pct,
+            avg_hold_hours,
+            cumulative_return_pct,
+            max_drawdown_pct,
+            profit_factor,
+            sharpe_ratio,
+            max_consecutive_wins,
+            max_consecutive_losses,
+        })
+    }
+
+    /// Walks the compounded equity curve once, tracking the running peak to
+    /// get both the final cumulative return and the largest peak-to-trough
+    /// decline along the way.
+    fn equity_curve(trades: &[TradeResult]) -> (f64, f64) {
+        let mut equity = 1.0;
+        let mut peak = 1.0;
+        let mut max_drawdown_pct = 0.0;
+
+        for t in trades {
+            equity *= 1.0 + t.profit_pct / 100.0;
+            peak = peak.max(equity);
+            let drawdown_pct = (peak - equity) / peak * 100.0;
+            max_drawdown_pct = max_drawdown_pct.max(drawdown_pct);
+        }
+
+        ((equity - 1.0) * 100.0, max_drawdown_pct)
+    }
+
+    fn profit_factor(trades: &[TradeResult]) -> f64 {
+        let gross_profit: f64 = trades
+            .iter()
+            .map(|t| t.profit_pct)
+            .filter(|p| *p > 0.0)
+            .sum();
+        let gross_loss: f64 = trades
+            .iter()
+            .map(|t| t.profit_pct)
+            .filter(|p| *p < 0.0)
+            .sum::<f64>()
+            .abs();
+
+        if gross_loss > 0.0 {
+            gross_profit / gross_loss
+        } else {
+            f64::INFINITY
+        }
+    }
+
+    fn sharpe_ratio(trades: &[TradeResult], avg_profit_pct: f64, avg_hold_hours: f64) -> f64 {
+        let variance = trades
+            .iter()
+            .map(|t| (t.profit_pct - avg_profit_pct).powi(2))
+            .sum::<f64>()
+            / trades.len() as f64;
+        let std_dev = variance.sqrt();
+        if std_dev == 0.0 || avg_hold_hours <= 0.0 {
+            return 0.0;
+        }
+
+        let trades_per_year = 365.0 * 24.0 / avg_hold_hours;
+        avg_profit_pct / std_dev * trades_per_year.sqrt()
+    }
+
+    fn consecutive_streaks(trades: &[TradeResult]) -> (usize, usize) {
+        let mut max_win_streak = 0;
+        let mut max_loss_streak = 0;
+        let mut cur_win_streak = 0;
+        let mut cur_loss_streak = 0;
+
+        for t in trades {
+            if t.profit_pct > 0.0 {
+                cur_win_streak += 1;
+                cur_loss_streak = 0;
+            } else {
+                cur_loss_streak += 1;
+                cur_win_streak = 0;
+            }
+            max_win_streak = max_win_streak.max(cur_win_streak);
+            max_loss_streak = max_loss_streak.max(cur_loss_streak);
+        }
+
+        (max_win_streak, max_loss_streak)
+    }
+}
+
+/// Prints a human-readable summary, same shape as fenxi1's `print_statistics`
+/// plus the risk-adjusted figures from `BacktestStats`.
+pub fn print_statistics(trades: &[TradeResult]) {
+    let Some(stats) = BacktestStats::compute(trades) else {
+        println!("\n没有完成的交易记录");
+        return;
+    };
+
+    println!("\n========== 回测统计结果 ==========");
+    println!("总交易次数: {}", stats.total_trades);
+    println!(
+        "盈利次数: {} | 亏损次数: {}",
+        stats.win_count, stats.loss_count
+    );
+    println!("胜率: {:.2}%", stats.win_rate_pct);
+    println!("----------------------------------");
+    println!("平均盈亏: {:.2}%", stats.avg_profit_pct);
+    println!("累计收益: {:.2}% (复利计算)", stats.cumulative_return_pct);
+    println!("最大回撤: {:.2}%", stats.max_drawdown_pct);
+    println!("----------------------------------");
+    println!("最大单笔盈利: {:.2}%", stats.max_profit_pct);
+    println!("最大单笔亏损: {:.2}%", stats.max_loss_pct);
+    println!("平均持仓时间: {:.1} 小时", stats.avg_hold_hours);
+    println!("----------------------------------");
+    println!("盈亏比 (profit factor): {:.2}", stats.profit_factor);
+    println!("夏普比率 (年化): {:.2}", stats.sharpe_ratio);
+    println!("最长连胜: {} | 最长连亏: {}", stats.max_consecutive_wins, stats.max_consecutive_losses);
+    println!("==================================\n");
+}
@@ -0,0 +1,53 @@
+use crate::analysis::ScanParams;
+use serde::Deserialize;
+use std::path::PathBuf;
+
+fn default_data_path() -> PathBuf {
+    PathBuf::from("data")
+}
+
+fn default_output_path() -> PathBuf {
+    PathBuf::from("output/signals.csv")
+}
+
+/// Top-level scanner configuration, loaded from a TOML file via `--config`
+/// so `data_path`/`output_path` and the default strategy thresholds can be
+/// tuned without a rebuild. `--strategies` still takes priority over
+/// `default_strategy` when scanning multiple named variants in one pass.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScannerConfig {
+    /// Root directory holding the `klines/`/`metrics/` archives.
+    #[serde(default = "default_data_path")]
+    pub data_path: PathBuf,
+    /// Where the scanned `BuySignal`s are written as CSV.
+    #[serde(default = "default_output_path")]
+    pub output_path: PathBuf,
+    /// Strategy used when `--strategies` isn't given.
+    #[serde(default)]
+    pub default_strategy: ScanParams,
+}
+
+impl Default for ScannerConfig {
+    fn default() -> Self {
+        Self {
+            data_path: default_data_path(),
+            output_path: default_output_path(),
+            default_strategy: ScanParams::default(),
+        }
+    }
+}
+
+impl ScannerConfig {
+    /// Loads a `ScannerConfig` from `path` if given, falling back to
+    /// `ScannerConfig::default()` when no config file is configured. A
+    /// present-but-unreadable/invalid file is a hard error rather than a
+    /// silent fallback, so a typo in `--config` doesn't quietly run with
+    /// defaults.
+    pub fn load(path: Option<&str>) -> anyhow::Result<Self> {
+        let Some(path) = path else {
+            return Ok(Self::default());
+        };
+        let contents = std::fs::read_to_string(path)?;
+        Ok(toml::from_str(&contents)?)
+    }
+}
@@ -0,0 +1,27 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum AppError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("CSV error: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("Parse error: {0}")]
+    Parse(String),
+
+    #[error("Insufficient data: required {required}, actual {actual}")]
+    InsufficientData { required: usize, actual: usize },
+
+    #[error("No data available for symbol: {0}")]
+    NoData(String),
+
+    #[error("Database error: {0}")]
+    Db(#[from] tokio_postgres::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+pub type Result<T> = std::result::Result<T, AppError>;
@@ -0,0 +1,129 @@
+use crate::error::{AppError, Result};
+use crate::models::Kline;
+use memmap2::Mmap;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Binary-native mirror of `Kline` used only for the bincode cache blob.
+///
+/// `Kline`'s own `Deserialize` impl runs every field through
+/// `deserialize_{i64,f64}_or_default`, which expects the CSV-style
+/// `Option<String>` shape Binance's archives come in. bincode writes plain
+/// fixint/float bytes, so decoding a `Kline` straight out of a bincode blob
+/// misreads those bytes as a string length and fails on (almost) every row.
+/// This plain-typed twin round-trips through bincode correctly; `Kline`
+/// itself is never bincode-(de)serialized.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedKline {
+    open_time: i64,
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+    volume: f64,
+    close_time: i64,
+    quote_volume: f64,
+    count: i64,
+    taker_buy_volume: f64,
+    taker_buy_quote_volume: f64,
+    ignore: i64,
+}
+
+impl From<&Kline> for CachedKline {
+    fn from(k: &Kline) -> Self {
+        Self {
+            open_time: k.open_time,
+            open: k.open,
+            high: k.high,
+            low: k.low,
+            close: k.close,
+            volume: k.volume,
+            close_time: k.close_time,
+            quote_volume: k.quote_volume,
+            count: k.count,
+            taker_buy_volume: k.taker_buy_volume,
+            taker_buy_quote_volume: k.taker_buy_quote_volume,
+            ignore: k.ignore,
+        }
+    }
+}
+
+impl From<CachedKline> for Kline {
+    fn from(k: CachedKline) -> Self {
+        Self {
+            open_time: k.open_time,
+            open: k.open,
+            high: k.high,
+            low: k.low,
+            close: k.close,
+            volume: k.volume,
+            close_time: k.close_time,
+            quote_volume: k.quote_volume,
+            count: k.count,
+            taker_buy_volume: k.taker_buy_volume,
+            taker_buy_quote_volume: k.taker_buy_quote_volume,
+            ignore: k.ignore,
+        }
+    }
+}
+
+/// Caches the parsed result of a full CSV sweep (e.g.
+/// `FastScanner::load_all_klines`) as a bincode blob under
+/// `data_path/.cache`, keyed by symbol/interval and invalidated whenever any
+/// source CSV is newer than the cache file. This turns repeated cold-start
+/// scans of the same archive into a single mmap + bincode decode instead of
+/// re-running the csv `Deserializer` over every row every time.
+pub struct KlineCache;
+
+impl KlineCache {
+    fn cache_path(data_path: &Path, symbol: &str, interval: &str) -> PathBuf {
+        data_path
+            .join(".cache")
+            .join(format!("{symbol}_{interval}.bin"))
+    }
+
+    /// Returns the cached klines for `symbol`/`interval` if a cache file
+    /// exists and is at least as new as every file in `source_files`.
+    /// Returns `None` on a cache miss, a stale cache, or any read error, so
+    /// the caller can transparently fall back to parsing `source_files`.
+    pub fn load(
+        data_path: &Path,
+        symbol: &str,
+        interval: &str,
+        source_files: &[PathBuf],
+    ) -> Option<Vec<Kline>> {
+        let cache_path = Self::cache_path(data_path, symbol, interval);
+        let cache_mtime = fs::metadata(&cache_path).and_then(|m| m.modified()).ok()?;
+
+        for file in source_files {
+            let source_mtime = fs::metadata(file).and_then(|m| m.modified()).ok()?;
+            if source_mtime > cache_mtime {
+                return None;
+            }
+        }
+
+        let file = File::open(&cache_path).ok()?;
+        // SAFETY: cache files are only ever replaced whole by `store` below,
+        // never modified in place while a reader might have them mapped.
+        let mmap = unsafe { Mmap::map(&file).ok()? };
+        let cached: Vec<CachedKline> = bincode::deserialize(&mmap[..]).ok()?;
+        Some(cached.into_iter().map(Kline::from).collect())
+    }
+
+    /// Persists `klines` as the cache for `symbol`/`interval`.
+    pub fn store(data_path: &Path, symbol: &str, interval: &str, klines: &[Kline]) -> Result<()> {
+        let cache_path = Self::cache_path(data_path, symbol, interval);
+        if let Some(parent) = cache_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let cached: Vec<CachedKline> = klines.iter().map(CachedKline::from).collect();
+        let bytes = bincode::serialize(&cached)
+            .map_err(|e| AppError::Parse(format!("cache encode: {e}")))?;
+        let mut file = File::create(&cache_path)?;
+        file.write_all(&bytes)?;
+        Ok(())
+    }
+}
@@ -1,16 +1,129 @@
-use crate::error::Result;
+use crate::error::{AppError, Result};
 use crate::models::Kline;
+use memmap2::Mmap;
 use std::collections::VecDeque;
 use std::fs::{self, File};
 use std::path::{Path, PathBuf};
 
+/// A single CSV file mapped read-only, indexed once into row byte offsets
+/// and a parallel `close_time` array so lookups are binary searches instead
+/// of re-reading already-consumed rows.
+struct MappedFile {
+    mmap: Mmap,
+    /// Byte offset of the start of each valid (non-header) row.
+    line_starts: Vec<usize>,
+    /// `close_time` of each row, parallel to `line_starts`. Ascending,
+    /// since rows within a file are already ordered.
+    close_times: Vec<i64>,
+}
+
+impl MappedFile {
+    fn open(path: &Path) -> Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: these are append-only archive CSVs under our data dir,
+        // not expected to be truncated while the loader holds them mapped.
+        let mmap = unsafe { Mmap::map(&file)? };
+        let (line_starts, close_times) = Self::build_index(&mmap);
+        Ok(Self {
+            mmap,
+            line_starts,
+            close_times,
+        })
+    }
+
+    fn build_index(mmap: &Mmap) -> (Vec<usize>, Vec<i64>) {
+        let data: &[u8] = &mmap[..];
+        let mut line_starts = Vec::new();
+        let mut close_times = Vec::new();
+
+        // Skip the CSV header line.
+        let mut pos = match find_newline(data, 0) {
+            Some(end) => end + 1,
+            None => return (line_starts, close_times),
+        };
+
+        while pos < data.len() {
+            let line_end = find_newline(data, pos).unwrap_or(data.len());
+            let line = trim_cr(&data[pos..line_end]);
+
+            if let Some(close_time) = parse_close_time(line) {
+                if close_time > 0 {
+                    line_starts.push(pos);
+                    close_times.push(close_time);
+                }
+            }
+
+            pos = line_end + 1;
+        }
+
+        (line_starts, close_times)
+    }
+
+    fn row_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    fn row_bytes(&self, idx: usize) -> &[u8] {
+        let data: &[u8] = &self.mmap[..];
+        let start = self.line_starts[idx];
+        let end = find_newline(data, start).unwrap_or(data.len());
+        trim_cr(&data[start..end])
+    }
+
+    fn deserialize_row(&self, idx: usize) -> Result<Option<Kline>> {
+        let kline = parse_kline_row(self.row_bytes(idx))?;
+        Ok(if kline.is_valid() { Some(kline) } else { None })
+    }
+
+    /// First index whose `close_time` is `>= target`.
+    fn lower_bound(&self, target: i64) -> usize {
+        self.close_times.partition_point(|&t| t < target)
+    }
+
+    /// First index whose `close_time` is `> target`.
+    fn upper_bound(&self, target: i64) -> usize {
+        self.close_times.partition_point(|&t| t <= target)
+    }
+}
+
+fn find_newline(data: &[u8], from: usize) -> Option<usize> {
+    data[from..].iter().position(|&b| b == b'\n').map(|i| i + from)
+}
+
+fn trim_cr(line: &[u8]) -> &[u8] {
+    if line.last() == Some(&b'\r') {
+        &line[..line.len() - 1]
+    } else {
+        line
+    }
+}
+
+/// Cheaply pulls just the `close_time` column (index 6 of the standard
+/// Binance kline layout) out of a raw row so index construction never
+/// pays for a full record deserialize.
+fn parse_close_time(line: &[u8]) -> Option<i64> {
+    let s = std::str::from_utf8(line).ok()?;
+    s.split(',').nth(6)?.trim().parse().ok()
+}
+
+fn parse_kline_row(line: &[u8]) -> Result<Kline> {
+    let s = std::str::from_utf8(line)
+        .map_err(|e| AppError::Parse(format!("invalid utf8 in kline row: {e}")))?;
+    let mut record = csv::StringRecord::new();
+    for field in s.split(',') {
+        record.push_field(field);
+    }
+    Ok(record.deserialize(None)?)
+}
+
 pub struct KlineLoader {
     symbol: String,
     interval: String,
     base_path: PathBuf,
     files: Vec<PathBuf>,
     current_file_idx: usize,
-    current_reader: Option<csv::Reader<File>>,
+    current: Option<MappedFile>,
+    current_row: usize,
     buffer: VecDeque<Kline>,
     window_size: usize,
 }
@@ -27,7 +140,8 @@ impl KlineLoader {
             base_path: base_path.to_path_buf(),
             files,
             current_file_idx: 0,
-            current_reader: None,
+            current: None,
+            current_row: 0,
             buffer: VecDeque::with_capacity(window_size + 100),
             window_size,
         })
@@ -52,27 +166,38 @@ impl KlineLoader {
         if self.current_file_idx >= self.files.len() {
             return Ok(false);
         }
-        let file = File::open(&self.files[self.current_file_idx])?;
-        self.current_reader = Some(csv::Reader::from_reader(file));
+        let mapped = MappedFile::open(&self.files[self.current_file_idx])?;
         self.current_file_idx += 1;
+        self.current_row = 0;
+        self.current = Some(mapped);
         Ok(true)
     }
 
-    fn read_next_kline(&mut self) -> Result<Option<Kline>> {
+    /// Skips exhausted/empty maps, opening the next file as needed, so
+    /// callers always see either a row to read or end-of-data.
+    fn ensure_current_row(&mut self) -> Result<bool> {
         loop {
-            if let Some(ref mut reader) = self.current_reader {
-                let mut record = csv::StringRecord::new();
-                if reader.read_record(&mut record)? {
-                    let kline: Kline = record.deserialize(None)?;
-                    if kline.is_valid() {
-                        return Ok(Some(kline));
-                    }
-                    continue;
+            if let Some(ref mapped) = self.current {
+                if self.current_row < mapped.row_count() {
+                    return Ok(true);
                 }
             }
             if !self.open_next_file()? {
+                return Ok(false);
+            }
+        }
+    }
+
+    fn read_next_kline(&mut self) -> Result<Option<Kline>> {
+        loop {
+            if !self.ensure_current_row()? {
                 return Ok(None);
             }
+            let row = self.current_row;
+            self.current_row += 1;
+            if let Some(kline) = self.current.as_ref().unwrap().deserialize_row(row)? {
+                return Ok(Some(kline));
+            }
         }
     }
 
@@ -122,45 +247,122 @@ impl KlineLoader {
         self.buffer.len() >= required
     }
 
+    /// Repositions the window so its last kline is the latest one with
+    /// `close_time <= target_time`, via binary search over the mapped
+    /// files' indices rather than a linear re-read. Only the current file
+    /// and later ones are searched: a target before the current file's
+    /// first row leaves the window as it is.
     pub fn sync_to(&mut self, target_time: i64) -> Result<()> {
-        while let Some(kline) = self.buffer.back() {
-            if kline.close_time <= target_time {
-                break;
+        self.seek(target_time, false)?;
+        Ok(())
+    }
+
+    /// Repositions the window so its last kline is the earliest one with
+    /// `close_time >= target_time`. Returns `false` once the data is
+    /// exhausted before reaching it.
+    pub fn advance_until(&mut self, target_time: i64) -> Result<bool> {
+        self.seek(target_time, true)
+    }
+
+    fn seek(&mut self, target_time: i64, forward: bool) -> Result<bool> {
+        let start_idx = self.current_file_idx.saturating_sub(1);
+        for file_idx in start_idx..self.files.len() {
+            let mapped = MappedFile::open(&self.files[file_idx])?;
+            if mapped.row_count() == 0 {
+                continue;
             }
-            if let Some(next) = self.read_next_kline()? {
-                self.buffer.push_back(next);
-                if self.buffer.len() > self.window_size {
-                    self.buffer.pop_front();
+            let first = mapped.close_times[0];
+            let last = *mapped.close_times.last().unwrap();
+
+            if forward {
+                if last < target_time {
+                    continue;
                 }
+                let row = mapped.lower_bound(target_time).min(mapped.row_count() - 1);
+                self.land_on(mapped, file_idx, row)?;
+                return Ok(true);
             } else {
-                break;
+                if first > target_time {
+                    break;
+                }
+                if last <= target_time && Self::next_file_has_le(&self.files, file_idx, target_time)? {
+                    // A later file actually contains a row <= target; let
+                    // the loop reach it instead of landing here.
+                    continue;
+                }
+                let row = mapped.upper_bound(target_time);
+                if row == 0 {
+                    continue;
+                }
+                self.land_on(mapped, file_idx, row - 1)?;
+                return Ok(true);
             }
         }
-        Ok(())
+        Ok(false)
     }
 
-    pub fn advance_until(&mut self, target_time: i64) -> Result<bool> {
-        loop {
-            if let Some(kline) = self.buffer.back() {
-                if kline.close_time >= target_time {
-                    return Ok(true);
+    /// Peeks past `file_idx` (skipping empty files) to check whether a
+    /// later file actually has a row `<= target_time`. Without this check,
+    /// `seek`'s reverse branch could skip past the current file's last row
+    /// — the correct answer for targets that fall exactly on a file
+    /// boundary — and then break on the next file's `first > target_time`,
+    /// leaving the window unrepositioned.
+    fn next_file_has_le(files: &[PathBuf], file_idx: usize, target_time: i64) -> Result<bool> {
+        for path in &files[file_idx + 1..] {
+            let next = MappedFile::open(path)?;
+            if next.row_count() == 0 {
+                continue;
+            }
+            return Ok(next.close_times[0] <= target_time);
+        }
+        Ok(false)
+    }
+
+    /// Rebuilds the window ending at `row` of `mapped`, pulling the tail of
+    /// the previous file across the boundary if `row` alone can't fill it.
+    fn land_on(&mut self, mapped: MappedFile, file_idx: usize, row: usize) -> Result<()> {
+        self.buffer.clear();
+
+        let take_here = (row + 1).min(self.window_size);
+        let take_prev = self.window_size - take_here;
+
+        if take_prev > 0 && file_idx > 0 {
+            let prev = MappedFile::open(&self.files[file_idx - 1])?;
+            let prev_rows = prev.row_count();
+            let take = take_prev.min(prev_rows);
+            for i in (prev_rows - take)..prev_rows {
+                if let Some(kline) = prev.deserialize_row(i)? {
+                    self.buffer.push_back(kline);
                 }
             }
-            if let Some(kline) = self.read_next_kline()? {
+        }
+
+        let start = (row + 1).saturating_sub(take_here);
+        for i in start..=row {
+            if let Some(kline) = mapped.deserialize_row(i)? {
                 self.buffer.push_back(kline);
-                if self.buffer.len() > self.window_size {
-                    self.buffer.pop_front();
-                }
-            } else {
-                return Ok(false);
             }
         }
+
+        self.current_row = row + 1;
+        self.current = Some(mapped);
+        self.current_file_idx = file_idx + 1;
+        Ok(())
     }
 
+    /// Klines in the current window with `close_time <= target_time`,
+    /// located by binary search rather than a filter over the whole buffer.
     pub fn get_klines_at_time(&self, target_time: i64) -> Vec<&Kline> {
-        self.buffer
-            .iter()
-            .filter(|k| k.close_time <= target_time)
-            .collect()
+        let mut lo = 0usize;
+        let mut hi = self.buffer.len();
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            if self.buffer[mid].close_time <= target_time {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        self.buffer.iter().take(lo).collect()
     }
 }
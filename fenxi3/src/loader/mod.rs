@@ -0,0 +1,5 @@
+mod kline_cache;
+mod kline_loader;
+
+pub use kline_cache::KlineCache;
+pub use kline_loader::KlineLoader;
@@ -0,0 +1,235 @@
+mod analysis;
+mod config;
+mod error;
+mod loader;
+mod models;
+mod output;
+mod store;
+
+use crate::analysis::{FastScanner, ScanParams};
+use crate::config::ScannerConfig;
+use crate::models::BuySignal;
+use crate::output::CsvWriter;
+use crate::store::{PostgresSignalStore, SignalStore};
+use clap::Parser;
+use indicatif::{ProgressBar, ProgressStyle};
+use rayon::prelude::*;
+use rayon::ThreadPoolBuilder;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tracing::{info, warn};
+
+/// Fast multi-symbol BOLL signal scanner
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Number of worker threads to scan symbols with. Set to 1 for a
+    /// deterministic, single-threaded run (useful in CI/backtests).
+    #[arg(long, default_value_t = num_cpus::get())]
+    scan_threads: usize,
+
+    /// Load independent 15m/30m/4h CSV archives per symbol instead of the
+    /// default of reading only 15m and synthesizing 30m/4h via resampling.
+    /// Only needed when the archived higher-timeframe data is expected to
+    /// differ from what resampling the 15m feed would produce.
+    #[arg(long)]
+    require_separate_intervals: bool,
+
+    /// TOML file listing named `[[strategies]]` (ScanParams) to scan in a
+    /// single pass over the same loaded klines. Without this, the scanner
+    /// runs `ScannerConfig::default_strategy` as a single "default" strategy.
+    #[arg(long)]
+    strategies: Option<String>,
+
+    /// TOML `ScannerConfig` file setting `data_path`/`output_path` and the
+    /// default strategy thresholds. Without this, built-in defaults apply.
+    #[arg(long)]
+    config: Option<String>,
+
+    /// Overrides `data_path` from `--config`/the built-in default.
+    #[arg(long)]
+    data_path: Option<String>,
+
+    /// Overrides `output_path` from `--config`/the built-in default.
+    #[arg(long)]
+    output_path: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StrategiesFile {
+    strategies: Vec<ScanParams>,
+}
+
+/// Loads named strategy variants from `path` (see `Args::strategies`), or
+/// falls back to a single strategy built from `default_strategy` (see
+/// `ScannerConfig`) when no file is given.
+fn load_strategies(
+    path: Option<&String>,
+    default_strategy: ScanParams,
+) -> anyhow::Result<Vec<ScanParams>> {
+    let Some(path) = path else {
+        return Ok(vec![default_strategy]);
+    };
+    let contents = fs::read_to_string(path)?;
+    let parsed: StrategiesFile = toml::from_str(&contents)?;
+    Ok(parsed.strategies)
+}
+
+fn get_symbols(data_path: &Path, resample: bool) -> Vec<String> {
+    let klines_path = data_path.join("klines");
+
+    let mut symbols: Vec<String> = fs::read_dir(&klines_path)
+        .ok()
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.path().is_dir())
+                .filter_map(|e| e.file_name().to_str().map(String::from))
+                .collect::<HashSet<String>>()
+        })
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|s| {
+            let has_15m = klines_path.join(s).join("15m").exists();
+            // Resampling only needs the base 15m feed; otherwise every
+            // timeframe must be separately archived for this symbol.
+            if resample {
+                return has_15m;
+            }
+            let has_30m = klines_path.join(s).join("30m").exists();
+            let has_4h = klines_path.join(s).join("4h").exists();
+            has_15m && has_30m && has_4h
+        })
+        .collect();
+
+    symbols.sort();
+    symbols
+}
+
+fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::from_default_env()
+                .add_directive("fenxi3=info".parse().unwrap()),
+        )
+        .init();
+
+    let args = Args::parse();
+    let config = ScannerConfig::load(args.config.as_deref())?;
+
+    let data_path = args
+        .data_path
+        .map(PathBuf::from)
+        .unwrap_or(config.data_path.clone());
+    let output_path = args
+        .output_path
+        .map(PathBuf::from)
+        .unwrap_or(config.output_path.clone());
+    let data_path = data_path.as_path();
+    let output_path = output_path.as_path();
+
+    info!(
+        "Starting parallel buy signal scanner ({} threads)...",
+        args.scan_threads
+    );
+
+    let symbols = get_symbols(data_path, !args.require_separate_intervals);
+    info!("Scanning {} symbols...", symbols.len());
+
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(args.scan_threads)
+        .build()?;
+
+    let pb = Arc::new(ProgressBar::new(symbols.len() as u64));
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} {msg}")
+            .unwrap()
+            .progress_chars("#>-"),
+    );
+
+    let writer = Arc::new(Mutex::new(CsvWriter::new(output_path)?));
+    let scanner = FastScanner::new(load_strategies(
+        args.strategies.as_ref(),
+        config.default_strategy.clone(),
+    )?);
+    let total_signals = Arc::new(Mutex::new(0usize));
+    let all_signals = Arc::new(Mutex::new(Vec::<BuySignal>::new()));
+
+    pool.install(|| {
+        symbols.par_iter().for_each(|symbol| {
+            let pb = Arc::clone(&pb);
+            pb.set_message(symbol.clone());
+
+            let result = if args.require_separate_intervals {
+                scanner.scan_symbol(data_path, symbol)
+            } else {
+                scanner.scan_symbol_resampled(data_path, symbol)
+            };
+
+            match result {
+                Ok(signals) if !signals.is_empty() => {
+                    write_signals(&writer, &signals);
+                    *total_signals.lock().unwrap() += signals.len();
+                    all_signals.lock().unwrap().extend(signals.clone());
+                    info!("{}: Found {} signals", symbol, signals.len());
+                }
+                Ok(_) => {}
+                Err(e) => warn!("{}: Error - {}", symbol, e),
+            }
+
+            pb.inc(1);
+        });
+    });
+
+    writer.lock().unwrap().flush()?;
+    pb.finish_with_message("Done!");
+
+    info!(
+        "Scan complete! Total signals: {}",
+        *total_signals.lock().unwrap()
+    );
+    info!("Results saved to: {:?}", output_path);
+
+    // Persisting to a SQL store is opt-in: the scanner stays fully usable
+    // from CSV alone when DATABASE_URL isn't set.
+    if std::env::var("DATABASE_URL").is_ok() {
+        let signals = Arc::try_unwrap(all_signals)
+            .map(|m| m.into_inner().unwrap())
+            .unwrap_or_default();
+        persist_signals(signals)?;
+    }
+
+    Ok(())
+}
+
+/// Upserts every signal found this run into Postgres, keyed on `(symbol,
+/// timestamp, strategy)` so re-scanning an overlapping CSV range never
+/// duplicates rows.
+fn persist_signals(signals: Vec<BuySignal>) -> anyhow::Result<()> {
+    if signals.is_empty() {
+        return Ok(());
+    }
+
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime.block_on(async {
+        let store = PostgresSignalStore::connect_from_env().await?;
+        store.upsert_signals(&signals).await?;
+        info!("Persisted {} signals to Postgres", signals.len());
+        Ok::<(), error::AppError>(())
+    })?;
+
+    Ok(())
+}
+
+/// Serializes a symbol's signals through the single shared writer, keeping
+/// output ordering within a symbol while letting workers scan concurrently.
+fn write_signals(writer: &Arc<Mutex<CsvWriter>>, signals: &[BuySignal]) {
+    let mut writer = writer.lock().unwrap();
+    if let Err(e) = writer.write_signals(signals) {
+        warn!("Failed to write signals to CSV: {}", e);
+    }
+}
@@ -0,0 +1,7 @@
+mod kline;
+mod metrics;
+mod signal;
+
+pub use kline::Kline;
+pub use metrics::Metrics;
+pub use signal::{BuySignal, TradeResult};
@@ -1,5 +1,5 @@
 use chrono::{TimeZone, Utc};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 fn format_timestamp(timestamp: i64) -> String {
     Utc.timestamp_millis_opt(timestamp)
@@ -8,7 +8,7 @@ fn format_timestamp(timestamp: i64) -> String {
         .unwrap_or_else(|| "Invalid".to_string())
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TradeResult {
     pub symbol: String,
     pub buy_time: i64,
@@ -19,6 +19,10 @@ pub struct TradeResult {
     pub sell_price: f64,
     pub profit_pct: f64,
     pub hold_hours: f64,
+    /// Which `ExitStrategy` rule closed the trade: `stop_loss`,
+    /// `take_profit`, `trailing_stop`, or `band_cross`, so a CSV of trades
+    /// can break down win/loss rate by exit type.
+    pub exit_reason: String,
 }
 
 impl TradeResult {
@@ -28,6 +32,7 @@ impl TradeResult {
         buy_price: f64,
         sell_time: i64,
         sell_price: f64,
+        exit_reason: String,
     ) -> Self {
         let profit_pct = (sell_price - buy_price) / buy_price * 100.0;
         let hold_hours = (sell_time - buy_time) as f64 / (1000.0 * 60.0 * 60.0);
@@ -42,41 +47,72 @@ impl TradeResult {
             sell_price,
             profit_pct,
             hold_hours,
+            exit_reason,
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BuySignal {
     pub timestamp: i64,
     pub datetime: String,
     pub symbol: String,
+    /// Name of the `ScanParams` strategy that produced this signal, so
+    /// results from several parameterizations scanned in one pass can be
+    /// told apart (and their hit rates compared) in the same CSV.
+    pub strategy: String,
     pub price: f64,
     pub boll_15m_upper: f64,
     pub boll_30m_middle: f64,
     pub boll_4h_middle: f64,
     pub volume_ratio: f64,
+    /// Entry bar's `VolumeFactors`, attached so a CSV of signals can be
+    /// correlated against realized `profit_pct` downstream.
+    pub relative_volume_ratio: f64,
+    pub ma_stack_aligned: bool,
+    pub taker_buy_ratio: f64,
+    /// Entry bar's KDJ, from the same `KdjValue` that `check_kdj_condition`
+    /// gated the signal on, so a CSV of signals can be correlated against
+    /// how oversold the entry actually was.
+    pub k: f64,
+    pub d: f64,
+    pub j: f64,
 }
 
 impl BuySignal {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         timestamp: i64,
         symbol: String,
+        strategy: String,
         price: f64,
         boll_15m_upper: f64,
         boll_30m_middle: f64,
         boll_4h_middle: f64,
         volume_ratio: f64,
+        relative_volume_ratio: f64,
+        ma_stack_aligned: bool,
+        taker_buy_ratio: f64,
+        k: f64,
+        d: f64,
+        j: f64,
     ) -> Self {
         Self {
             timestamp,
             datetime: format_timestamp(timestamp),
             symbol,
+            strategy,
             price,
             boll_15m_upper,
             boll_30m_middle,
             boll_4h_middle,
             volume_ratio,
+            relative_volume_ratio,
+            ma_stack_aligned,
+            taker_buy_ratio,
+            k,
+            d,
+            j,
         }
     }
 }
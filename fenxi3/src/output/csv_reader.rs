@@ -0,0 +1,35 @@
+use crate::error::Result;
+use crate::models::{BuySignal, TradeResult};
+use std::fs::File;
+use std::path::Path;
+
+/// Reads back `BuySignal`/`TradeResult` CSVs written by `CsvWriter`, for
+/// diffing two runs, resuming a backtest, or feeding historical signals into
+/// a new evaluation pass.
+pub struct CsvReader {
+    reader: csv::Reader<File>,
+}
+
+impl CsvReader {
+    pub fn new(path: &Path) -> Result<Self> {
+        let file = File::open(path)?;
+        let reader = csv::Reader::from_reader(file);
+        Ok(Self { reader })
+    }
+
+    pub fn read_signals(mut self) -> Result<Vec<BuySignal>> {
+        let mut signals = Vec::new();
+        for result in self.reader.deserialize::<BuySignal>() {
+            signals.push(result?);
+        }
+        Ok(signals)
+    }
+
+    pub fn read_trades(mut self) -> Result<Vec<TradeResult>> {
+        let mut trades = Vec::new();
+        for result in self.reader.deserialize::<TradeResult>() {
+            trades.push(result?);
+        }
+        Ok(trades)
+    }
+}
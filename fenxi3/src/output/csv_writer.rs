@@ -0,0 +1,281 @@
+use crate::analysis::BacktestStats;
+use crate::error::Result;
+use crate::models::{BuySignal, TradeResult};
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+
+/// Which wire format `CsvWriter::with_config` serializes records as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Csv,
+    /// One `serde_json`-encoded line per record, for log-ingestion
+    /// pipelines that expect newline-delimited JSON over CSV.
+    Ndjson,
+}
+
+impl Default for OutputFormat {
+    fn default() -> Self {
+        OutputFormat::Csv
+    }
+}
+
+/// Knobs for `CsvWriter::with_config`: delimiter/quoting/headers only apply
+/// to `OutputFormat::Csv`; `OutputFormat::Ndjson` ignores them and always
+/// writes one JSON object per line.
+#[derive(Debug, Clone)]
+pub struct WriterConfig {
+    pub delimiter: u8,
+    pub write_headers: bool,
+    pub quote_style: csv::QuoteStyle,
+    pub format: OutputFormat,
+}
+
+impl Default for WriterConfig {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            write_headers: true,
+            quote_style: csv::QuoteStyle::Necessary,
+            format: OutputFormat::Csv,
+        }
+    }
+}
+
+enum Sink {
+    Csv(csv::Writer<File>),
+    Ndjson(File),
+}
+
+impl Sink {
+    fn serialize<T: Serialize>(&mut self, record: &T) -> Result<()> {
+        match self {
+            Sink::Csv(writer) => writer.serialize(record)?,
+            Sink::Ndjson(file) => {
+                serde_json::to_writer(&mut *file, record)?;
+                file.write_all(b"\n")?;
+            }
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        match self {
+            Sink::Csv(writer) => writer.flush()?,
+            Sink::Ndjson(file) => file.flush()?,
+        }
+        Ok(())
+    }
+}
+
+/// A record (or a flush request) queued for `CsvWriter::spawn_background`'s
+/// worker thread.
+pub enum WriteMsg {
+    Signal(BuySignal),
+    Trade(TradeResult),
+    Flush,
+}
+
+/// Producer-side handle for a `CsvWriter::spawn_background` worker. Sends
+/// are fire-and-forget: a closed channel just means the worker already
+/// exited (e.g. after an I/O error), which `JoinHandle::join` surfaces, so
+/// the producer doesn't need to check every send.
+#[derive(Clone)]
+pub struct SignalSender {
+    tx: mpsc::Sender<WriteMsg>,
+}
+
+impl SignalSender {
+    pub fn send_signal(&self, signal: BuySignal) {
+        let _ = self.tx.send(WriteMsg::Signal(signal));
+    }
+
+    pub fn send_trade(&self, trade: TradeResult) {
+        let _ = self.tx.send(WriteMsg::Trade(trade));
+    }
+
+    pub fn flush(&self) {
+        let _ = self.tx.send(WriteMsg::Flush);
+    }
+}
+
+/// Tracks the temp-file-and-rename state for a `CsvWriter` opened via
+/// `new_atomic`: writes land in `tmp_path` until `commit` renames it over
+/// `final_path`, so a reader never observes a half-written file.
+struct AtomicWrite {
+    tmp_path: PathBuf,
+    final_path: PathBuf,
+}
+
+pub struct CsvWriter {
+    sink: Sink,
+    atomic: Option<AtomicWrite>,
+}
+
+impl CsvWriter {
+    pub fn new(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = File::create(path)?;
+        Ok(Self {
+            sink: Sink::Csv(csv::Writer::from_writer(file)),
+            atomic: None,
+        })
+    }
+
+    /// Like `new`, but backed by `config` instead of `csv::Writer`'s
+    /// defaults: a custom delimiter/quote style, headers toggled off, or an
+    /// `OutputFormat::Ndjson` sink entirely.
+    pub fn with_config(path: &Path, config: WriterConfig) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = File::create(path)?;
+        let sink = match config.format {
+            OutputFormat::Csv => Sink::Csv(
+                csv::WriterBuilder::new()
+                    .delimiter(config.delimiter)
+                    .has_headers(config.write_headers)
+                    .quote_style(config.quote_style)
+                    .from_writer(file),
+            ),
+            OutputFormat::Ndjson => Sink::Ndjson(file),
+        };
+        Ok(Self {
+            sink,
+            atomic: None,
+        })
+    }
+
+    /// Opens `path` in append mode, creating it (and its parent directories)
+    /// if it doesn't exist yet. Headers are only serialized when `path` is
+    /// newly created or empty, so appending to an already-populated
+    /// partition file never duplicates a header row mid-file.
+    pub fn open_append(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let write_headers = !path.exists() || fs::metadata(path)?.len() == 0;
+        let file = fs::OpenOptions::new().create(true).append(true).open(path)?;
+        let writer = csv::WriterBuilder::new()
+            .has_headers(write_headers)
+            .from_writer(file);
+        Ok(Self {
+            sink: Sink::Csv(writer),
+            atomic: None,
+        })
+    }
+
+    /// Like `new`, but writes to a sibling `<path>.tmp` file and only
+    /// replaces `path` once `commit` is called, so a crash or early return
+    /// mid-run leaves the previous good output in place instead of a
+    /// half-written file at `path`.
+    pub fn new_atomic(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp_path = path.with_extension("csv.tmp");
+        let file = File::create(&tmp_path)?;
+        Ok(Self {
+            sink: Sink::Csv(csv::Writer::from_writer(file)),
+            atomic: Some(AtomicWrite {
+                tmp_path,
+                final_path: path.to_path_buf(),
+            }),
+        })
+    }
+
+    /// Flushes the writer and, for a writer opened via `new_atomic`, renames
+    /// the temp file over the final path (atomic within a filesystem). A
+    /// plain `new` writer has nothing to rename, so this is just a flush.
+    pub fn commit(mut self) -> Result<()> {
+        self.sink.flush()?;
+        if let Some(atomic) = self.atomic.take() {
+            fs::rename(&atomic.tmp_path, &atomic.final_path)?;
+        }
+        Ok(())
+    }
+
+    pub fn write_signal(&mut self, signal: &BuySignal) -> Result<()> {
+        self.sink.serialize(signal)
+    }
+
+    pub fn write_signals(&mut self, signals: &[BuySignal]) -> Result<()> {
+        for signal in signals {
+            self.write_signal(signal)?;
+        }
+        Ok(())
+    }
+
+    pub fn write_trade(&mut self, trade: &TradeResult) -> Result<()> {
+        self.sink.serialize(trade)
+    }
+
+    pub fn write_trades(&mut self, trades: &[TradeResult]) -> Result<()> {
+        for trade in trades {
+            self.write_trade(trade)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a single-row summary CSV, meant for a `stats.csv` next to
+    /// `trades.csv` rather than the multi-row signal/trade files above.
+    pub fn write_stats(&mut self, stats: &BacktestStats) -> Result<()> {
+        self.sink.serialize(stats)
+    }
+
+    pub fn flush(&mut self) -> Result<()> {
+        self.sink.flush()
+    }
+
+    /// Spawns a worker thread owning a `CsvWriter` for `path`, draining
+    /// `WriteMsg`s off an mpsc channel so a producer (e.g. a `scan_symbol`
+    /// worker) never blocks on disk I/O. Dropping every clone of the
+    /// returned `SignalSender` closes the channel and ends the worker's
+    /// loop; joining the handle then flushes and surfaces any I/O error hit
+    /// along the way.
+    pub fn spawn_background(path: &Path) -> Result<(SignalSender, thread::JoinHandle<Result<()>>)> {
+        let mut writer = CsvWriter::new(path)?;
+        let (tx, rx) = mpsc::channel::<WriteMsg>();
+
+        let handle = thread::spawn(move || -> Result<()> {
+            for msg in rx {
+                match msg {
+                    WriteMsg::Signal(signal) => writer.write_signal(&signal)?,
+                    WriteMsg::Trade(trade) => writer.write_trade(&trade)?,
+                    WriteMsg::Flush => writer.flush()?,
+                }
+            }
+            writer.flush()
+        });
+
+        Ok((SignalSender { tx }, handle))
+    }
+}
+
+impl Drop for CsvWriter {
+    /// A `new_atomic` writer dropped without `commit` (early return, panic,
+    /// or an `Err` propagated before the run finishes) never got renamed
+    /// into place; delete its temp file so the previous good output at
+    /// `final_path` is never clobbered by a half-written one.
+    fn drop(&mut self) {
+        if let Some(atomic) = self.atomic.take() {
+            let _ = fs::remove_file(&atomic.tmp_path);
+        }
+    }
+}
+
+/// Writes `stats` as pretty-printed JSON, for consumers that want the
+/// backtest summary without parsing a CSV row.
+pub fn write_stats_json(path: &Path, stats: &BacktestStats) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let file = File::create(path)?;
+    serde_json::to_writer_pretty(file, stats)?;
+    Ok(())
+}
@@ -0,0 +1,9 @@
+mod csv_reader;
+mod csv_writer;
+mod partitioned_store;
+
+pub use csv_reader::CsvReader;
+pub use csv_writer::{
+    write_stats_json, CsvWriter, OutputFormat, SignalSender, WriteMsg, WriterConfig,
+};
+pub use partitioned_store::PartitionedCsvStore;
@@ -0,0 +1,60 @@
+use crate::error::Result;
+use crate::models::{BuySignal, TradeResult};
+use crate::output::CsvWriter;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Routes records to `datadir/<key[0]>/.../<key[n]>.csv` — every key
+/// component but the last becomes a nested directory, the last becomes the
+/// file stem (e.g. `vec![symbol, date]` routes to
+/// `datadir/<symbol>/<date>.csv`) — keeping one append-mode `CsvWriter` per
+/// partition open for the life of the store. This supports
+/// incremental/streaming runs that accumulate signals across days without
+/// rereading and rewriting a single monolithic file.
+pub struct PartitionedCsvStore {
+    datadir: PathBuf,
+    writers: HashMap<PathBuf, CsvWriter>,
+}
+
+impl PartitionedCsvStore {
+    pub fn new(datadir: PathBuf) -> Self {
+        Self {
+            datadir,
+            writers: HashMap::new(),
+        }
+    }
+
+    fn partition_path(&self, key: &[String]) -> PathBuf {
+        let mut path = self.datadir.clone();
+        if let Some((file_stem, dirs)) = key.split_last() {
+            for dir in dirs {
+                path = path.join(dir);
+            }
+            path = path.join(format!("{file_stem}.csv"));
+        }
+        path
+    }
+
+    fn writer_for(&mut self, key: &[String]) -> Result<&mut CsvWriter> {
+        let path = self.partition_path(key);
+        if !self.writers.contains_key(&path) {
+            self.writers.insert(path.clone(), CsvWriter::open_append(&path)?);
+        }
+        Ok(self.writers.get_mut(&path).expect("just inserted"))
+    }
+
+    pub fn write_signal(&mut self, key: &[String], signal: &BuySignal) -> Result<()> {
+        self.writer_for(key)?.write_signal(signal)
+    }
+
+    pub fn write_trade(&mut self, key: &[String], trade: &TradeResult) -> Result<()> {
+        self.writer_for(key)?.write_trade(trade)
+    }
+
+    pub fn flush_all(&mut self) -> Result<()> {
+        for writer in self.writers.values_mut() {
+            writer.flush()?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,16 @@
+mod postgres_store;
+
+pub use postgres_store::PostgresSignalStore;
+
+use crate::error::Result;
+use crate::models::BuySignal;
+use async_trait::async_trait;
+
+/// Durable, queryable home for scanned `BuySignal`s, separate from the CSV
+/// output `scan_symbol` always produces. Implementations must make
+/// `upsert_signals` idempotent on `(symbol, timestamp, strategy)` so re-scanning a CSV
+/// range that was already persisted doesn't duplicate rows.
+#[async_trait]
+pub trait SignalStore: Send + Sync {
+    async fn upsert_signals(&self, signals: &[BuySignal]) -> Result<()>;
+}
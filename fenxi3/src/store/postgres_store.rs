@@ -0,0 +1,119 @@
+use super::SignalStore;
+use crate::error::Result;
+use crate::models::BuySignal;
+use async_trait::async_trait;
+use tokio_postgres::{Client, NoTls};
+use tracing::warn;
+
+/// Batch size for the multi-row upsert statement. Keeps the parameter count
+/// (9 per row) well under Postgres' ~65k bind-parameter limit while still
+/// amortizing round-trips across a whole scan's signals.
+const UPSERT_BATCH_SIZE: usize = 500;
+
+pub struct PostgresSignalStore {
+    client: Client,
+}
+
+impl PostgresSignalStore {
+    /// Connects using `DATABASE_URL` (a standard `postgres://...` connection
+    /// string) and ensures the `buy_signals` table exists.
+    pub async fn connect_from_env() -> Result<Self> {
+        let database_url = std::env::var("DATABASE_URL")
+            .unwrap_or_else(|_| "host=localhost user=postgres dbname=prophet".to_string());
+
+        let (client, connection) = tokio_postgres::connect(&database_url, NoTls).await?;
+
+        tokio::spawn(async move {
+            if let Err(e) = connection.await {
+                warn!("postgres connection closed: {e}");
+            }
+        });
+
+        client
+            .batch_execute(
+                "
+                CREATE TABLE IF NOT EXISTS buy_signals (
+                    symbol TEXT NOT NULL,
+                    timestamp BIGINT NOT NULL,
+                    strategy TEXT NOT NULL,
+                    datetime TEXT NOT NULL,
+                    price DOUBLE PRECISION NOT NULL,
+                    boll_15m_upper DOUBLE PRECISION NOT NULL,
+                    boll_30m_middle DOUBLE PRECISION NOT NULL,
+                    boll_4h_middle DOUBLE PRECISION NOT NULL,
+                    volume_ratio DOUBLE PRECISION NOT NULL,
+                    PRIMARY KEY (symbol, timestamp, strategy)
+                )
+                ",
+            )
+            .await?;
+
+        Ok(Self { client })
+    }
+
+    /// Builds and executes one parameterized `INSERT ... ON CONFLICT DO
+    /// UPDATE` statement for a batch of signals, keyed on `(symbol,
+    /// timestamp, strategy)` so re-persisting the same signal is a no-op
+    /// write.
+    async fn upsert_batch(&self, batch: &[BuySignal]) -> Result<()> {
+        if batch.is_empty() {
+            return Ok(());
+        }
+
+        let mut params: Vec<&(dyn tokio_postgres::types::ToSql + Sync)> =
+            Vec::with_capacity(batch.len() * 9);
+        let mut values_sql = Vec::with_capacity(batch.len());
+
+        for (i, signal) in batch.iter().enumerate() {
+            let base = i * 9;
+            values_sql.push(format!(
+                "(${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${})",
+                base + 1,
+                base + 2,
+                base + 3,
+                base + 4,
+                base + 5,
+                base + 6,
+                base + 7,
+                base + 8,
+                base + 9,
+            ));
+            params.push(&signal.symbol);
+            params.push(&signal.timestamp);
+            params.push(&signal.strategy);
+            params.push(&signal.datetime);
+            params.push(&signal.price);
+            params.push(&signal.boll_15m_upper);
+            params.push(&signal.boll_30m_middle);
+            params.push(&signal.boll_4h_middle);
+            params.push(&signal.volume_ratio);
+        }
+
+        let statement = format!(
+            "INSERT INTO buy_signals
+                (symbol, timestamp, strategy, datetime, price, boll_15m_upper, boll_30m_middle, boll_4h_middle, volume_ratio)
+             VALUES {}
+             ON CONFLICT (symbol, timestamp, strategy) DO UPDATE SET
+                datetime = EXCLUDED.datetime,
+                price = EXCLUDED.price,
+                boll_15m_upper = EXCLUDED.boll_15m_upper,
+                boll_30m_middle = EXCLUDED.boll_30m_middle,
+                boll_4h_middle = EXCLUDED.boll_4h_middle,
+                volume_ratio = EXCLUDED.volume_ratio",
+            values_sql.join(", ")
+        );
+
+        self.client.execute(&statement, &params).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SignalStore for PostgresSignalStore {
+    async fn upsert_signals(&self, signals: &[BuySignal]) -> Result<()> {
+        for batch in signals.chunks(UPSERT_BATCH_SIZE) {
+            self.upsert_batch(batch).await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+use super::BollingerBands;
+use std::collections::VecDeque;
+
+/// Bollinger accumulator that updates in O(1) per closed candle instead of
+/// recomputing the full window, by keeping a running `sum`/`sum_sq` over
+/// the trailing `period` closes and adjusting it by `sum += new - old` as
+/// candles enter/leave the window.
+pub struct IncrementalBollinger {
+    period: usize,
+    std_dev_multiplier: f64,
+    window: VecDeque<f64>,
+    sum: f64,
+    sum_sq: f64,
+}
+
+impl IncrementalBollinger {
+    pub fn new(period: usize, std_dev_multiplier: f64) -> Self {
+        Self {
+            period,
+            std_dev_multiplier,
+            window: VecDeque::with_capacity(period),
+            sum: 0.0,
+            sum_sq: 0.0,
+        }
+    }
+
+    /// Feeds one closed candle's close price. Returns the updated bands
+    /// once `period` closes have accumulated, `None` before that.
+    pub fn push(&mut self, close: f64) -> Option<BollingerBands> {
+        self.window.push_back(close);
+        self.sum += close;
+        self.sum_sq += close * close;
+
+        if self.window.len() > self.period {
+            let old = self.window.pop_front().unwrap();
+            self.sum -= old;
+            self.sum_sq -= old * old;
+        }
+
+        self.current()
+    }
+
+    /// Returns the bands for the window as it stands, without feeding a new
+    /// close. `None` until `period` closes have accumulated.
+    pub fn current(&self) -> Option<BollingerBands> {
+        if self.window.len() < self.period {
+            return None;
+        }
+
+        let mean = self.sum / self.period as f64;
+        let variance = (self.sum_sq / self.period as f64 - mean * mean).max(0.0);
+        let std_dev = variance.sqrt();
+
+        Some(BollingerBands {
+            upper: mean + std_dev * self.std_dev_multiplier,
+            middle: mean,
+            lower: mean - std_dev * self.std_dev_multiplier,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_incremental_matches_batch() {
+        let closes: Vec<f64> = (1..=20).map(|x| x as f64).collect();
+
+        let mut acc = IncrementalBollinger::new(20, 2.0);
+        let mut bands = None;
+        for &c in &closes {
+            bands = acc.push(c);
+        }
+
+        let bands = bands.expect("window should be full after 20 pushes");
+        assert!((bands.middle - 10.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_none_before_period_filled() {
+        let mut acc = IncrementalBollinger::new(20, 2.0);
+        for c in 1..10 {
+            assert!(acc.push(c as f64).is_none());
+        }
+    }
+
+    #[test]
+    fn test_slides_window_on_overflow() {
+        let mut acc = IncrementalBollinger::new(3, 2.0);
+        acc.push(1.0);
+        acc.push(2.0);
+        let bands = acc.push(3.0).unwrap();
+        assert!((bands.middle - 2.0).abs() < 0.001);
+
+        // Pushing a 4th value should drop the 1.0 and slide the mean up.
+        let bands = acc.push(6.0).unwrap();
+        assert!((bands.middle - (2.0 + 3.0 + 6.0) / 3.0).abs() < 0.001);
+    }
+}
@@ -0,0 +1,5 @@
+mod bollinger;
+mod incremental;
+
+pub use bollinger::{BollingerBands, BollingerCalculator};
+pub use incremental::IncrementalBollinger;
@@ -1,6 +1,6 @@
 use crate::config::BinanceConfig;
 use crate::error::{AppError, Result};
-use crate::models::{Kline, OpenInterest, OpenInterestHist};
+use crate::models::{Kline, LongShortRatio, OpenInterest, OpenInterestHist};
 
 use super::RateLimiter;
 use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, USER_AGENT};
@@ -47,7 +47,7 @@ impl BinanceClient {
         Self {
             client,
             config,
-            rate_limiter: Arc::new(RateLimiter::new(300)),
+            rate_limiter: Arc::new(RateLimiter::keyed(300, 300)),
             semaphore: Arc::new(Semaphore::new(5)),
             perpetual_symbols: Arc::new(tokio::sync::RwLock::new(HashSet::new())),
         }
@@ -162,6 +162,58 @@ impl BinanceClient {
         Ok(hist)
     }
 
+    /// 获取大户持仓量多空比 (用于实时监控, 而非回测用的历史ZIP)
+    pub async fn get_top_long_short_ratio(&self, symbol: &str) -> Result<Vec<LongShortRatio>> {
+        let url = format!(
+            "{}/futures/data/topLongShortPositionRatio?symbol={}&period=5m&limit=30",
+            self.config.futures_base_url, symbol
+        );
+
+        let response = self.client.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(AppError::BinanceApi(format!(
+                "topLongShortPositionRatio failed: {text}"
+            )));
+        }
+
+        let ratios: Vec<LongShortRatio> = response.json().await?;
+        Ok(ratios)
+    }
+
+    /// 监控用途的持仓量+多空比快照 (自带限流, 供 `monitor::OiMonitor` 轮询调用)
+    pub async fn get_oi_monitor_snapshot(&self, symbol: &str) -> Result<(f64, Option<f64>)> {
+        self.rate_limiter.acquire_for("open_interest").await;
+        let oi = self.get_open_interest(symbol).await?;
+
+        self.rate_limiter.acquire_for("long_short_ratio").await;
+        let ratio = self
+            .get_top_long_short_ratio(symbol)
+            .await
+            .ok()
+            .and_then(|hist| hist.last().map(LongShortRatio::long_short_ratio_f64));
+
+        Ok((oi.open_interest_f64(), ratio))
+    }
+
+    /// 当前持仓量 + 3天最低持仓量 (自带限流, 不拉K线; 供 `live::LiveEvaluator`
+    /// 在无需重新回填历史K线时单独刷新持仓量条件)
+    pub async fn get_oi_with_min_3d(&self, symbol: &str) -> Result<(f64, f64)> {
+        self.rate_limiter.acquire_for("open_interest").await;
+        let current_oi = self.get_open_interest(symbol).await?;
+
+        self.rate_limiter.acquire_for("open_interest_hist").await;
+        let hist_oi = self.get_open_interest_hist(symbol).await?;
+
+        let min_oi = hist_oi
+            .iter()
+            .map(|o| o.sum_open_interest_f64())
+            .fold(f64::MAX, f64::min);
+
+        Ok((current_oi.open_interest_f64(), min_oi))
+    }
+
     /// 获取多时间周期K线数据
     pub async fn get_multi_timeframe_klines(
         &self,
@@ -170,13 +222,13 @@ impl BinanceClient {
         let _permit = self.semaphore.acquire().await.unwrap();
 
         // 依次获取三个时间周期的K线
-        self.rate_limiter.acquire().await;
+        self.rate_limiter.acquire_for("klines").await;
         let klines_15m = self.get_futures_klines(symbol, "15m").await?;
 
-        self.rate_limiter.acquire().await;
+        self.rate_limiter.acquire_for("klines").await;
         let klines_30m = self.get_futures_klines(symbol, "30m").await?;
 
-        self.rate_limiter.acquire().await;
+        self.rate_limiter.acquire_for("klines").await;
         let klines_4h = self.get_futures_klines(symbol, "4h").await?;
 
         Ok((klines_15m, klines_30m, klines_4h))
@@ -191,10 +243,10 @@ impl BinanceClient {
         let (klines_15m, klines_30m, klines_4h) = self.get_multi_timeframe_klines(symbol).await?;
 
         // 获取持仓量数据
-        self.rate_limiter.acquire().await;
+        self.rate_limiter.acquire_for("open_interest").await;
         let current_oi = self.get_open_interest(symbol).await?;
 
-        self.rate_limiter.acquire().await;
+        self.rate_limiter.acquire_for("open_interest_hist").await;
         let hist_oi = self.get_open_interest_hist(symbol).await?;
 
         // 计算3天最低持仓量
@@ -1,6 +1,6 @@
 use crate::config::CoinGeckoConfig;
 use crate::error::{AppError, Result};
-use crate::models::CoinInfo;
+use crate::models::{CoinInfo, CoinOhlc, MarketChart};
 
 use super::RateLimiter;
 use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, USER_AGENT};
@@ -73,4 +73,46 @@ impl CoinGeckoClient {
     pub fn to_binance_symbol(coin: &CoinInfo) -> String {
         format!("{}USDT", coin.symbol.to_uppercase())
     }
+
+    /// 获取现货OHLC (`days` 取值受 CoinGecko 限制: 1/7/14/30/90/180/365/max)
+    pub async fn get_coin_ohlc(&self, coin_id: &str, days: u32) -> Result<Vec<CoinOhlc>> {
+        self.rate_limiter.acquire().await;
+
+        let url = format!(
+            "{}/coins/{}/ohlc?vs_currency=usd&days={}",
+            self.config.base_url, coin_id, days
+        );
+
+        let response = self.client.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(AppError::CoinGeckoApi(format!("Status {}: {}", status, text)));
+        }
+
+        let rows: Vec<Vec<serde_json::Value>> = response.json().await?;
+        rows.iter().map(|row| CoinOhlc::from_coingecko_response(row)).collect()
+    }
+
+    /// 获取价格/市值/成交量的时间序列 (`days` 为向前查询的天数)
+    pub async fn get_market_chart(&self, coin_id: &str, days: u32) -> Result<MarketChart> {
+        self.rate_limiter.acquire().await;
+
+        let url = format!(
+            "{}/coins/{}/market_chart?vs_currency=usd&days={}",
+            self.config.base_url, coin_id, days
+        );
+
+        let response = self.client.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(AppError::CoinGeckoApi(format!("Status {}: {}", status, text)));
+        }
+
+        let value: serde_json::Value = response.json().await?;
+        MarketChart::from_coingecko_response(&value)
+    }
 }
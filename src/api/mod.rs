@@ -1,7 +1,9 @@
 mod binance;
 mod coingecko;
+mod price_source;
 mod rate_limiter;
 
 pub use binance::BinanceClient;
 pub use coingecko::CoinGeckoClient;
+pub use price_source::{FixedSource, PriceSource};
 pub use rate_limiter::RateLimiter;
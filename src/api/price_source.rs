@@ -0,0 +1,95 @@
+use crate::error::{AppError, Result};
+use crate::models::{CoinInfo, Kline};
+
+use super::{BinanceClient, CoinGeckoClient};
+
+/// A source of the market data the analysis path needs, decoupled from any
+/// particular exchange/aggregator. `BinanceClient` and `CoinGeckoClient`
+/// each cover one half of it; `FixedSource` replays canned data so
+/// `AnalyzedCoin::meets_all_conditions` can be unit-tested deterministically.
+#[async_trait::async_trait]
+pub trait PriceSource: Send + Sync {
+    async fn fetch_klines(&self, symbol: &str, interval: &str) -> Result<Vec<Kline>>;
+
+    /// Returns `(current_open_interest, min_open_interest_3d)`.
+    async fn fetch_open_interest(&self, symbol: &str) -> Result<(f64, f64)>;
+
+    async fn list_coins(&self, n: usize) -> Result<Vec<CoinInfo>>;
+}
+
+#[async_trait::async_trait]
+impl PriceSource for BinanceClient {
+    async fn fetch_klines(&self, symbol: &str, interval: &str) -> Result<Vec<Kline>> {
+        self.get_futures_klines(symbol, interval).await
+    }
+
+    async fn fetch_open_interest(&self, symbol: &str) -> Result<(f64, f64)> {
+        let current = self.get_open_interest(symbol).await?;
+        let hist = self.get_open_interest_hist(symbol).await?;
+        let min_oi = hist
+            .iter()
+            .map(|o| o.sum_open_interest_f64())
+            .fold(f64::MAX, f64::min);
+        Ok((current.open_interest_f64(), min_oi))
+    }
+
+    async fn list_coins(&self, _n: usize) -> Result<Vec<CoinInfo>> {
+        Err(AppError::BinanceApi(
+            "Binance is a futures data source and does not list coins by market cap".to_string(),
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl PriceSource for CoinGeckoClient {
+    async fn fetch_klines(&self, _symbol: &str, _interval: &str) -> Result<Vec<Kline>> {
+        Err(AppError::CoinGeckoApi(
+            "CoinGecko does not expose futures kline data".to_string(),
+        ))
+    }
+
+    async fn fetch_open_interest(&self, _symbol: &str) -> Result<(f64, f64)> {
+        Err(AppError::CoinGeckoApi(
+            "CoinGecko does not expose open interest".to_string(),
+        ))
+    }
+
+    async fn list_coins(&self, n: usize) -> Result<Vec<CoinInfo>> {
+        self.get_top_coins(n).await
+    }
+}
+
+/// Replays fixed `Kline`/open-interest/coin data instead of calling out to
+/// an exchange. Intended for tests that exercise the analysis conditions
+/// without a network dependency.
+#[derive(Debug, Clone, Default)]
+pub struct FixedSource {
+    pub klines: Vec<Kline>,
+    pub open_interest: (f64, f64),
+    pub coins: Vec<CoinInfo>,
+}
+
+impl FixedSource {
+    pub fn new(klines: Vec<Kline>, open_interest: (f64, f64), coins: Vec<CoinInfo>) -> Self {
+        Self {
+            klines,
+            open_interest,
+            coins,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl PriceSource for FixedSource {
+    async fn fetch_klines(&self, _symbol: &str, _interval: &str) -> Result<Vec<Kline>> {
+        Ok(self.klines.clone())
+    }
+
+    async fn fetch_open_interest(&self, _symbol: &str) -> Result<(f64, f64)> {
+        Ok(self.open_interest)
+    }
+
+    async fn list_coins(&self, n: usize) -> Result<Vec<CoinInfo>> {
+        Ok(self.coins.iter().take(n).cloned().collect())
+    }
+}
@@ -1,23 +1,91 @@
+use governor::state::keyed::DefaultKeyedStateStore;
 use governor::{
     clock::DefaultClock,
     state::{InMemoryState, NotKeyed},
-    Quota, RateLimiter as GovRateLimiter,
+    Jitter, Quota, RateLimiter as GovRateLimiter,
 };
 use std::num::NonZeroU32;
+use std::time::Duration;
+
+enum Inner {
+    /// One shared bucket for every caller — fine when an upstream exposes a
+    /// single effective quota.
+    Direct(GovRateLimiter<NotKeyed, InMemoryState, DefaultClock>),
+    /// One independent bucket per key, so e.g. Binance's klines endpoint and
+    /// its open-interest endpoint (separate quotas upstream) don't starve
+    /// each other out of one shared budget.
+    Keyed(GovRateLimiter<String, DefaultKeyedStateStore<String>, DefaultClock>),
+}
 
 pub struct RateLimiter {
-    limiter: GovRateLimiter<NotKeyed, InMemoryState, DefaultClock>,
+    inner: Inner,
+    jitter: Jitter,
 }
 
 impl RateLimiter {
+    /// A single shared quota of `requests_per_minute`, with no burst above it.
     pub fn new(requests_per_minute: u32) -> Self {
-        let quota = Quota::per_minute(NonZeroU32::new(requests_per_minute).unwrap());
+        Self::with_burst(requests_per_minute, requests_per_minute)
+    }
+
+    /// Same as `new`, but `burst` may exceed `requests_per_minute` so a
+    /// caller can spend a short burst before falling back to the per-minute
+    /// average.
+    pub fn with_burst(requests_per_minute: u32, burst: u32) -> Self {
+        Self {
+            inner: Inner::Direct(GovRateLimiter::direct(quota(requests_per_minute, burst))),
+            jitter: Jitter::new(Duration::ZERO, Duration::ZERO),
+        }
+    }
+
+    /// A quota per key (e.g. per host/endpoint) instead of one global quota.
+    /// Use `acquire_for`/`try_acquire_for` to address a specific bucket.
+    pub fn keyed(requests_per_minute: u32, burst: u32) -> Self {
         Self {
-            limiter: GovRateLimiter::direct(quota),
+            inner: Inner::Keyed(GovRateLimiter::keyed(quota(requests_per_minute, burst))),
+            jitter: Jitter::new(Duration::ZERO, Duration::ZERO),
         }
     }
 
+    /// Adds up to `max` random jitter to every wait, so many callers that
+    /// hit the limit at once don't all wake up and retry in lockstep.
+    pub fn with_jitter(mut self, max: Duration) -> Self {
+        self.jitter = Jitter::up_to(max);
+        self
+    }
+
+    /// Waits for a slot on the default ("") bucket. On a `keyed` limiter
+    /// this shares one bucket across every caller that doesn't pass a key.
     pub async fn acquire(&self) {
-        self.limiter.until_ready().await;
+        self.acquire_for("").await
     }
+
+    pub async fn acquire_for(&self, key: &str) {
+        match &self.inner {
+            Inner::Direct(limiter) => limiter.until_ready_with_jitter(self.jitter).await,
+            Inner::Keyed(limiter) => {
+                limiter
+                    .until_key_ready_with_jitter(&key.to_string(), self.jitter)
+                    .await
+            }
+        }
+    }
+
+    /// Non-blocking variant of `acquire`: returns `false` instead of
+    /// waiting, so a caller can shed load rather than queue behind it.
+    pub fn try_acquire(&self) -> bool {
+        self.try_acquire_for("")
+    }
+
+    pub fn try_acquire_for(&self, key: &str) -> bool {
+        match &self.inner {
+            Inner::Direct(limiter) => limiter.check().is_ok(),
+            Inner::Keyed(limiter) => limiter.check_key(&key.to_string()).is_ok(),
+        }
+    }
+}
+
+fn quota(requests_per_minute: u32, burst: u32) -> Quota {
+    Quota::per_minute(NonZeroU32::new(requests_per_minute).unwrap())
+        .allow_burst(NonZeroU32::new(burst.max(1)).unwrap())
 }
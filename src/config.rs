@@ -0,0 +1,181 @@
+use config::{Config, ConfigError, File};
+use serde::Deserialize;
+use std::env;
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Settings {
+    pub coingecko: CoinGeckoConfig,
+    pub binance: BinanceConfig,
+    pub analysis: AnalysisConfig,
+    #[serde(skip)]
+    pub email: EmailConfig,
+    pub scheduler: SchedulerConfig,
+    #[serde(default)]
+    pub telegram: Option<TelegramConfig>,
+    #[serde(default)]
+    pub webhook: Option<WebhookConfig>,
+    #[serde(default)]
+    pub monitor: Option<MonitorConfig>,
+    #[serde(default)]
+    pub live: Option<LiveConfig>,
+    #[serde(default)]
+    pub api: Option<ApiConfig>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct CoinGeckoConfig {
+    pub base_url: String,
+    pub top_n: usize,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct BinanceConfig {
+    pub spot_base_url: String,
+    pub futures_base_url: String,
+    pub kline_limit: u32,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct AnalysisConfig {
+    pub boll_period: usize,
+    pub boll_std_dev: f64,
+    pub history_check_count: usize, // 50
+    pub history_threshold: usize,   // 25
+    pub oi_multiplier: f64,         // 持仓量乘数，如 0.9
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct EmailConfig {
+    pub smtp_server: String,
+    pub smtp_port: u16,
+    pub username: String,
+    pub password: String,
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct SchedulerConfig {
+    pub interval_minutes: u64,
+    /// UTC hours (0-23) at which a consolidated digest is emitted.
+    #[serde(default = "default_digest_hours_utc")]
+    pub digest_hours_utc: Vec<u32>,
+    /// UTC hour of the daily summary slot, at which the polling loop sends
+    /// each notifier's heartbeat; match digests follow `digest_hours_utc`
+    /// only.
+    #[serde(default = "default_daily_summary_hour_utc")]
+    pub daily_summary_hour_utc: u32,
+    /// Minutes a coin must stay un-re-notified after firing, unless its
+    /// condition set changes (a rollover).
+    #[serde(default = "default_cooldown_minutes")]
+    pub cooldown_minutes: i64,
+    /// Where last-fired state is persisted so a restart doesn't re-spam.
+    #[serde(default = "default_state_file")]
+    pub state_file: String,
+}
+
+fn default_digest_hours_utc() -> Vec<u32> {
+    (0..24).collect()
+}
+
+fn default_daily_summary_hour_utc() -> u32 {
+    15
+}
+
+fn default_cooldown_minutes() -> i64 {
+    240
+}
+
+fn default_state_file() -> String {
+    "data/digest_state.json".to_string()
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct TelegramConfig {
+    pub bot_token: String,
+    pub chat_id: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct WebhookConfig {
+    pub url: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct MonitorConfig {
+    /// 要实时监控的合约符号 (如 "BTCUSDT")
+    pub watchlist: Vec<String>,
+    /// 两次轮询之间的间隔
+    #[serde(default = "default_monitor_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+    /// 滚动窗口保留的持仓量采样点数
+    #[serde(default = "default_monitor_window_size")]
+    pub window_size: usize,
+    /// 持仓量在窗口内的变动超过这个百分比即触发告警
+    #[serde(default = "default_monitor_oi_change_pct_threshold")]
+    pub oi_change_pct_threshold: f64,
+    /// 大户多空比超过/低于 1/threshold 即触发告警
+    #[serde(default = "default_monitor_long_short_ratio_threshold")]
+    pub long_short_ratio_threshold: f64,
+    /// 额外推送告警的webhook (不设置则只打印)
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+}
+
+fn default_monitor_poll_interval_secs() -> u64 {
+    30
+}
+
+fn default_monitor_window_size() -> usize {
+    10
+}
+
+fn default_monitor_oi_change_pct_threshold() -> f64 {
+    5.0
+}
+
+fn default_monitor_long_short_ratio_threshold() -> f64 {
+    2.0
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct LiveConfig {
+    /// 通过websocket实时评估的合约符号 (如 "BTCUSDT")，替代轮询分析这些符号
+    pub watchlist: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ApiConfig {
+    /// HTTP服务监听地址 (如 "0.0.0.0:8080")
+    pub bind_addr: String,
+}
+
+impl Settings {
+    pub fn load() -> Result<Self, ConfigError> {
+        dotenvy::dotenv().ok();
+
+        let config = Config::builder()
+            .add_source(File::with_name("config/default").required(false))
+            .build()?;
+
+        let mut settings: Settings = config.try_deserialize()?;
+
+        // Load email config from environment variables
+        settings.email = EmailConfig {
+            smtp_server: env::var("EMAIL_SMTP_SERVER")
+                .unwrap_or_else(|_| "smtp.163.com".to_string()),
+            smtp_port: env::var("EMAIL_SMTP_PORT")
+                .unwrap_or_else(|_| "994".to_string())
+                .parse()
+                .unwrap_or(994),
+            username: env::var("EMAIL_USERNAME")
+                .map_err(|_| ConfigError::NotFound("EMAIL_USERNAME".into()))?,
+            password: env::var("EMAIL_PASSWORD")
+                .map_err(|_| ConfigError::NotFound("EMAIL_PASSWORD".into()))?,
+            from: env::var("EMAIL_FROM").map_err(|_| ConfigError::NotFound("EMAIL_FROM".into()))?,
+            to: env::var("EMAIL_TO").map_err(|_| ConfigError::NotFound("EMAIL_TO".into()))?,
+        };
+
+        Ok(settings)
+    }
+}
@@ -25,6 +25,12 @@ pub enum AppError {
 
     #[error("Symbol not found on Binance: {0}")]
     SymbolNotFound(String),
+
+    #[error("WebSocket stream error: {0}")]
+    StreamError(String),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
 }
 
 pub type Result<T> = std::result::Result<T, AppError>;
@@ -0,0 +1,78 @@
+use crate::error::Result;
+use crate::events::{EventBus, MatchEvent};
+
+use serde::Serialize;
+use std::io::Write;
+use std::path::PathBuf;
+use tokio::task::JoinHandle;
+use tracing::{error, warn};
+
+#[derive(Debug, Serialize)]
+struct AuditRecord {
+    fired_at: chrono::DateTime<chrono::Utc>,
+    symbol: String,
+    futures_symbol: Option<String>,
+    current_price: f64,
+    current_oi: f64,
+    min_oi_3d: f64,
+}
+
+impl From<&MatchEvent> for AuditRecord {
+    fn from(event: &MatchEvent) -> Self {
+        Self {
+            fired_at: event.fired_at,
+            symbol: event.coin.coin.symbol.to_uppercase(),
+            futures_symbol: event.coin.coin.futures_symbol.clone(),
+            current_price: event.coin.current_price,
+            current_oi: event.coin.current_oi,
+            min_oi_3d: event.coin.min_oi_3d,
+        }
+    }
+}
+
+/// A replayable NDJSON record of every coin that ever met all conditions,
+/// kept independently of whatever notifiers happen to be configured.
+/// Subscribes to the `EventBus` and appends one line per event; falling
+/// behind only drops entries from the audit log, it never blocks analysis.
+pub struct AuditLogWriter;
+
+impl AuditLogWriter {
+    /// Spawns the background task and returns its handle so the caller can
+    /// await it at shutdown if it wants to.
+    pub fn spawn(path: impl Into<PathBuf>, bus: &EventBus) -> JoinHandle<()> {
+        let path = path.into();
+        let mut receiver = bus.subscribe();
+
+        tokio::spawn(async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => {
+                        if let Err(e) = Self::append(&path, &AuditRecord::from(&event)) {
+                            error!("Audit log write failed: {}", e);
+                        }
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("Audit log lagged, dropped {} match event(s)", skipped);
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    fn append(path: &PathBuf, record: &AuditRecord) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+
+        writeln!(file, "{}", serde_json::to_string(record)?)?;
+        Ok(())
+    }
+}
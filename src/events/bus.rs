@@ -0,0 +1,45 @@
+use crate::models::AnalyzedCoin;
+
+use chrono::{DateTime, Utc};
+use tokio::sync::broadcast;
+
+/// One coin that met all conditions, stamped with when the analyzer fired it.
+#[derive(Debug, Clone)]
+pub struct MatchEvent {
+    pub coin: AnalyzedCoin,
+    pub fired_at: DateTime<Utc>,
+}
+
+/// Fans `MatchEvent`s out to however many consumers care, without the
+/// analyzer waiting on any of them. The audit log and the HTTP API's
+/// `SignalStore` each hold their own `subscribe()`d receiver; notifiers do
+/// not subscribe and are called directly by the polling loop. A slow or
+/// absent consumer only drops its own oldest events (`Lagged`) instead of
+/// blocking publication for everyone else.
+pub struct EventBus {
+    sender: broadcast::Sender<MatchEvent>,
+}
+
+impl EventBus {
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { sender }
+    }
+
+    /// Publishes an event to every current subscriber. Returns the number of
+    /// subscribers it was delivered to; `0` (no one listening) is not an
+    /// error — the bus has no memory of whether anyone is subscribed.
+    pub fn publish(&self, event: MatchEvent) -> usize {
+        self.sender.send(event).unwrap_or(0)
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<MatchEvent> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new(256)
+    }
+}
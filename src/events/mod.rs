@@ -0,0 +1,5 @@
+mod audit;
+mod bus;
+
+pub use audit::AuditLogWriter;
+pub use bus::{EventBus, MatchEvent};
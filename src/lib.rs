@@ -0,0 +1,12 @@
+pub mod analysis;
+pub mod api;
+pub mod config;
+pub mod error;
+pub mod events;
+pub mod live;
+pub mod models;
+pub mod monitor;
+pub mod notification;
+pub mod scheduler;
+pub mod server;
+pub mod stream;
@@ -0,0 +1,245 @@
+use crate::analysis::{BollingerCalculator, IncrementalBollinger};
+use crate::api::BinanceClient;
+use crate::config::AnalysisConfig;
+use crate::error::Result;
+use crate::events::{EventBus, MatchEvent};
+use crate::models::{AnalyzedCoin, CoinInfo, Kline, MultiTimeframeBoll};
+use crate::scheduler::DigestScheduler;
+use crate::stream::SymbolStream;
+
+use chrono::Utc;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use tokio::sync::{watch, Mutex};
+use tracing::{info, warn};
+
+/// Live counterpart to `main.rs`'s `analyze_coin`: instead of a REST poll
+/// once per scheduler cycle, maintains one symbol's 6-condition state
+/// incrementally off websocket kline closes, publishing a `MatchEvent` to
+/// the shared `EventBus` the moment every condition is met rather than
+/// waiting for the next cycle. Checks a `DigestScheduler` (the polling
+/// path's cooldown logic, with its own state) so a symbol that just fired
+/// respects the cooldown instead of re-notifying on every subsequent
+/// candle close.
+pub struct LiveEvaluator {
+    coin: CoinInfo,
+    futures_symbol: String,
+    binance: Arc<BinanceClient>,
+    calculator: BollingerCalculator,
+    history_check_count: usize,
+    history_threshold: usize,
+    oi_multiplier: f64,
+    event_bus: Arc<EventBus>,
+    scheduler: Arc<Mutex<DigestScheduler>>,
+
+    boll_15m: IncrementalBollinger,
+    boll_30m: IncrementalBollinger,
+    boll_4h: IncrementalBollinger,
+    history_15m: VecDeque<Kline>,
+    history_30m: VecDeque<Kline>,
+    history_4h: VecDeque<Kline>,
+
+    current_oi: f64,
+    min_oi_3d: f64,
+
+    kline_15m_rx: watch::Receiver<Kline>,
+    kline_30m_rx: watch::Receiver<Kline>,
+    kline_4h_rx: watch::Receiver<Kline>,
+}
+
+impl LiveEvaluator {
+    /// Backfills all three timeframes once over REST to seed the
+    /// accumulators and history windows, then opens one websocket stream
+    /// per timeframe for the ongoing live feed. `SymbolStream` only
+    /// exposes its latest kline, not the backfilled series it used to seed
+    /// itself, so this performs its own (duplicate, but one-off) backfill
+    /// rather than threading history out of `SymbolStream`.
+    pub async fn connect(
+        binance: Arc<BinanceClient>,
+        coin: CoinInfo,
+        futures_symbol: String,
+        config: &AnalysisConfig,
+        event_bus: Arc<EventBus>,
+        scheduler: Arc<Mutex<DigestScheduler>>,
+    ) -> Result<Self> {
+        let (klines_15m, klines_30m, klines_4h, current_oi, min_oi_3d) =
+            binance.get_analysis_data(&futures_symbol).await?;
+
+        let mut boll_15m = IncrementalBollinger::new(config.boll_period, config.boll_std_dev);
+        let mut boll_30m = IncrementalBollinger::new(config.boll_period, config.boll_std_dev);
+        let mut boll_4h = IncrementalBollinger::new(config.boll_period, config.boll_std_dev);
+        for k in &klines_15m {
+            boll_15m.push(k.close);
+        }
+        for k in &klines_30m {
+            boll_30m.push(k.close);
+        }
+        for k in &klines_4h {
+            boll_4h.push(k.close);
+        }
+
+        let history_15m = Self::seed_history(&klines_15m, config.history_check_count);
+        let history_30m = Self::seed_history(&klines_30m, config.history_check_count);
+        let history_4h = Self::seed_history(&klines_4h, config.history_check_count);
+
+        let kline_15m_rx = SymbolStream::connect(binance.clone(), futures_symbol.clone(), "15m".to_string(), 1)
+            .await?
+            .kline_watch();
+        let kline_30m_rx = SymbolStream::connect(binance.clone(), futures_symbol.clone(), "30m".to_string(), 1)
+            .await?
+            .kline_watch();
+        let kline_4h_rx = SymbolStream::connect(binance.clone(), futures_symbol.clone(), "4h".to_string(), 1)
+            .await?
+            .kline_watch();
+
+        Ok(Self {
+            coin,
+            futures_symbol,
+            binance,
+            calculator: BollingerCalculator::new(config.boll_period, config.boll_std_dev),
+            history_check_count: config.history_check_count,
+            history_threshold: config.history_threshold,
+            oi_multiplier: config.oi_multiplier,
+            event_bus,
+            scheduler,
+            boll_15m,
+            boll_30m,
+            boll_4h,
+            history_15m,
+            history_30m,
+            history_4h,
+            current_oi,
+            min_oi_3d,
+            kline_15m_rx,
+            kline_30m_rx,
+            kline_4h_rx,
+        })
+    }
+
+    fn seed_history(klines: &[Kline], history_check_count: usize) -> VecDeque<Kline> {
+        klines
+            .iter()
+            .rev()
+            .take(history_check_count)
+            .rev()
+            .cloned()
+            .collect()
+    }
+
+    fn push_bounded(history: &mut VecDeque<Kline>, kline: Kline, cap: usize) {
+        history.push_back(kline);
+        while history.len() > cap {
+            history.pop_front();
+        }
+    }
+
+    /// Runs until all three kline feeds have closed. The stream tasks that
+    /// feed them reconnect forever on their own, so in practice this runs
+    /// for the life of the process.
+    pub async fn run(mut self) {
+        loop {
+            tokio::select! {
+                Ok(()) = self.kline_15m_rx.changed() => {
+                    let k = self.kline_15m_rx.borrow().clone();
+                    self.boll_15m.push(k.close);
+                    Self::push_bounded(&mut self.history_15m, k, self.history_check_count);
+                }
+                Ok(()) = self.kline_30m_rx.changed() => {
+                    let k = self.kline_30m_rx.borrow().clone();
+                    self.boll_30m.push(k.close);
+                    Self::push_bounded(&mut self.history_30m, k, self.history_check_count);
+                }
+                Ok(()) = self.kline_4h_rx.changed() => {
+                    let k = self.kline_4h_rx.borrow().clone();
+                    self.boll_4h.push(k.close);
+                    Self::push_bounded(&mut self.history_4h, k, self.history_check_count);
+
+                    // 3-day min OI only needs refreshing once a 4h candle closes.
+                    match self.binance.get_oi_with_min_3d(&self.futures_symbol).await {
+                        Ok((current_oi, min_oi_3d)) => {
+                            self.current_oi = current_oi;
+                            self.min_oi_3d = min_oi_3d;
+                        }
+                        Err(e) => warn!(symbol = %self.futures_symbol, error = %e, "failed to refresh OI snapshot"),
+                    }
+                }
+                else => break,
+            }
+
+            self.evaluate().await;
+        }
+    }
+
+    async fn evaluate(&self) {
+        let (Some(boll_15m), Some(boll_30m), Some(boll_4h)) = (
+            self.boll_15m.current(),
+            self.boll_30m.current(),
+            self.boll_4h.current(),
+        ) else {
+            return;
+        };
+
+        let current_price = self
+            .history_15m
+            .back()
+            .map(|k| k.close)
+            .unwrap_or(self.coin.current_price);
+
+        let history_15m: Vec<Kline> = self.history_15m.iter().cloned().collect();
+        let history_30m: Vec<Kline> = self.history_30m.iter().cloned().collect();
+
+        let analyzed = AnalyzedCoin {
+            coin: self.coin.clone(),
+            current_price,
+            boll: MultiTimeframeBoll {
+                boll_15m_upper: boll_15m.upper,
+                boll_15m_middle: boll_15m.middle,
+                boll_30m_upper: boll_30m.upper,
+                boll_30m_middle: boll_30m.middle,
+                boll_4h_upper: boll_4h.upper,
+                boll_4h_middle: boll_4h.middle,
+            },
+            cond1_price_above_15m_upper: current_price > boll_15m.upper,
+            cond2_price_above_30m_middle: current_price > boll_30m.middle,
+            cond3_price_above_4h_middle: current_price > boll_4h.middle,
+            cond4_15m_history_below_upper: self.calculator.check_history_condition(
+                &history_15m,
+                boll_15m.upper,
+                self.history_check_count,
+                self.history_threshold,
+            ),
+            cond5_30m_history_below_middle: self.calculator.check_history_condition(
+                &history_30m,
+                boll_30m.middle,
+                self.history_check_count,
+                self.history_threshold,
+            ),
+            cond6_oi_condition: self.current_oi * self.oi_multiplier > self.min_oi_3d,
+            current_oi: self.current_oi,
+            min_oi_3d: self.min_oi_3d,
+        };
+
+        if !analyzed.meets_all_conditions() {
+            return;
+        }
+
+        let now = Utc::now();
+        let due = match self.scheduler.lock().await.filter_due(&[&analyzed], now) {
+            Ok(due) => due,
+            Err(e) => {
+                warn!(symbol = %self.futures_symbol, error = %e, "failed to check digest cooldown");
+                return;
+            }
+        };
+
+        if due.is_empty() {
+            return;
+        }
+
+        info!(symbol = %self.futures_symbol, "LIVE MATCH: meets all 6 conditions");
+        self.event_bus.publish(MatchEvent {
+            coin: analyzed,
+            fired_at: now,
+        });
+    }
+}
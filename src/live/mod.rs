@@ -0,0 +1,3 @@
+mod evaluator;
+
+pub use evaluator::LiveEvaluator;
@@ -1,10 +1,18 @@
 use prophet::analysis::BollingerCalculator;
 use prophet::api::{BinanceClient, CoinGeckoClient};
-use prophet::config::Settings;
+use prophet::config::{SchedulerConfig, Settings};
+use prophet::events::{AuditLogWriter, EventBus, MatchEvent};
+use prophet::live::LiveEvaluator;
 use prophet::models::{AnalyzedCoin, CoinInfo, MultiTimeframeBoll};
-use prophet::notification::EmailNotifier;
+use prophet::monitor::OiMonitor;
+use prophet::notification::{EmailNotifier, Notifier, TelegramNotifier, WebhookNotifier};
+use prophet::scheduler::DigestScheduler;
+use prophet::server::{self, SignalStore};
 
+use chrono::Utc;
+use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::Mutex;
 use tracing::{error, info, warn};
 
 #[tokio::main]
@@ -30,7 +38,13 @@ async fn main() -> anyhow::Result<()> {
         settings.analysis.boll_period,
         settings.analysis.boll_std_dev,
     );
-    let notifier = EmailNotifier::new(settings.email.clone())?;
+    let mut notifiers: Vec<Box<dyn Notifier>> = vec![Box::new(EmailNotifier::new(settings.email.clone())?)];
+    if let Some(telegram) = &settings.telegram {
+        notifiers.push(Box::new(TelegramNotifier::new(telegram.clone())));
+    }
+    if let Some(webhook) = &settings.webhook {
+        notifiers.push(Box::new(WebhookNotifier::new(webhook.clone())));
+    }
 
     let interval = Duration::from_secs(settings.scheduler.interval_minutes * 60);
 
@@ -44,47 +58,102 @@ async fn main() -> anyhow::Result<()> {
         }
     }
 
-    // Counter for heartbeat email - if no email sent for 100 cycles, send a heartbeat
-    let mut no_email_counter: u32 = 0;
-    const HEARTBEAT_THRESHOLD: u32 = 100;
+    let mut scheduler = DigestScheduler::new(settings.scheduler.clone())?;
+
+    // Every coin that meets all conditions is published here the moment it's
+    // found, independent of the digest/cooldown gating below. The audit log
+    // and, when `api` is configured, the HTTP signal store below subscribe;
+    // other consumers can subscribe the same way without the analyzer
+    // knowing they exist.
+    let event_bus = Arc::new(EventBus::default());
+    AuditLogWriter::spawn("data/audit_log.ndjson", &event_bus);
+
+    // Same subscribe-and-don't-block pattern as the audit log, but keeps
+    // the matches in memory so `api.bind_addr` can serve them as JSON
+    // instead of everything going out only via email/webhook.
+    if let Some(api_config) = settings.api.clone() {
+        let signal_store = SignalStore::spawn(&event_bus);
+        tokio::spawn(async move {
+            if let Err(e) = server::serve(&api_config.bind_addr, signal_store).await {
+                error!(error = %e, "signal HTTP API exited");
+            }
+        });
+    }
+
+    if let Some(monitor_config) = settings.monitor.clone() {
+        let monitor_binance = BinanceClient::new(settings.binance.clone());
+        let mut monitor = OiMonitor::new(monitor_binance, monitor_config);
+        tokio::spawn(async move { monitor.run().await });
+    }
+
+    // Symbols on the live watchlist are evaluated off the websocket feed
+    // instead of waiting for the next polling cycle below; they share the
+    // polling path's cooldown logic via their own `DigestScheduler`, kept on
+    // a separate state file so the two don't clobber each other's fired
+    // records.
+    if let Some(live_config) = settings.live.clone() {
+        let live_scheduler_config = SchedulerConfig {
+            state_file: "data/live_digest_state.json".to_string(),
+            ..settings.scheduler.clone()
+        };
+        let live_scheduler = Arc::new(Mutex::new(DigestScheduler::new(live_scheduler_config)?));
+        let live_binance = Arc::new(BinanceClient::new(settings.binance.clone()));
+
+        for symbol in live_config.watchlist {
+            let coin = CoinInfo {
+                id: symbol.to_lowercase(),
+                symbol: symbol.clone(),
+                name: symbol.clone(),
+                current_price: 0.0,
+                market_cap: 0.0,
+                market_cap_rank: None,
+                binance_symbol: None,
+                futures_symbol: Some(symbol.clone()),
+            };
+
+            match LiveEvaluator::connect(
+                live_binance.clone(),
+                coin,
+                symbol.clone(),
+                &settings.analysis,
+                event_bus.clone(),
+                live_scheduler.clone(),
+            )
+            .await
+            {
+                Ok(evaluator) => {
+                    tokio::spawn(evaluator.run());
+                }
+                Err(e) => error!(symbol = %symbol, error = %e, "failed to start live evaluator"),
+            }
+        }
+    }
 
     loop {
         info!("Starting analysis cycle...");
+        let now = Utc::now();
 
-        match run_analysis(&coingecko, &binance, &calculator, &notifier, &settings).await {
+        match run_analysis(&coingecko, &binance, &calculator, &notifiers, &settings, &mut scheduler, &event_bus, now).await {
             Ok(count) => {
                 info!(
                     "Analysis completed. Found {} coins meeting all 7 conditions",
                     count
                 );
-
-                if count > 0 {
-                    // Email was sent, reset counter
-                    no_email_counter = 0;
-                } else {
-                    // No email sent this cycle
-                    no_email_counter += 1;
-                    info!("No email counter: {}/{}", no_email_counter, HEARTBEAT_THRESHOLD);
-
-                    if no_email_counter >= HEARTBEAT_THRESHOLD {
-                        info!("Sending heartbeat email to confirm system is running...");
-                        match notifier.send_heartbeat().await {
-                            Ok(_) => {
-                                info!("Heartbeat email sent successfully!");
-                                no_email_counter = 0;
-                            }
-                            Err(e) => {
-                                error!("Failed to send heartbeat email: {}", e);
-                            }
-                        }
-                    }
-                }
             }
             Err(e) => {
                 error!("Analysis failed: {}", e);
             }
         }
 
+        if scheduler.daily_summary_due(now) {
+            info!("Daily summary window reached, sending heartbeat...");
+            for notifier in &notifiers {
+                if let Err(e) = notifier.send_heartbeat().await {
+                    error!("Heartbeat notifier failed: {}", e);
+                }
+            }
+        }
+
         info!(
             "Sleeping for {} minutes...",
             settings.scheduler.interval_minutes
@@ -97,8 +166,11 @@ async fn run_analysis(
     coingecko: &CoinGeckoClient,
     binance: &BinanceClient,
     calculator: &BollingerCalculator,
-    notifier: &EmailNotifier,
+    notifiers: &[Box<dyn Notifier>],
     settings: &Settings,
+    scheduler: &mut DigestScheduler,
+    event_bus: &EventBus,
+    now: chrono::DateTime<Utc>,
 ) -> anyhow::Result<usize> {
     // Step 1: Get top N coins from CoinGecko
     info!(
@@ -131,6 +203,10 @@ async fn run_analysis(
                         "MATCH: {} ({}) meets all 7 conditions!",
                         coin.name, futures_symbol
                     );
+                    event_bus.publish(MatchEvent {
+                        coin: analyzed.clone(),
+                        fired_at: now,
+                    });
                 }
                 analyzed_coins.push(analyzed);
                 processed += 1;
@@ -164,9 +240,10 @@ async fn run_analysis(
         .filter(|c| c.meets_all_conditions())
         .collect();
 
+    let matching_count = matching.len();
     info!(
         "Found {} coins meeting all 7 conditions (out of {} analyzed)",
-        matching.len(),
+        matching_count,
         analyzed_coins.len()
     );
 
@@ -196,17 +273,40 @@ async fn run_analysis(
         }
         println!("=====================================================\n");
 
-        // Send email notification
-        info!("Sending email notification...");
-        match notifier.send_alert_v2(&matching).await {
-            Ok(_) => info!("Email sent successfully!"),
-            Err(e) => error!("Email failed: {} (results printed above)", e),
+        // Suppress coins that already fired within their cooldown window
+        // and haven't rolled over onto a different condition set; only
+        // deliver the digest itself at the configured UTC windows.
+        let digest_due = scheduler.hourly_digest_due(now);
+        let due_symbols = if digest_due {
+            scheduler.filter_due(&matching, now)?
+        } else {
+            Vec::new()
+        };
+        let due: Vec<&AnalyzedCoin> = matching
+            .into_iter()
+            .filter(|c| due_symbols.contains(&c.coin.symbol.to_uppercase()))
+            .collect();
+
+        if !digest_due {
+            info!("Not a digest window yet, deferring {} match(es)", matching_count);
+        } else if due.is_empty() {
+            info!("All matches already notified within their cooldown window, skipping digest");
+        } else {
+            // Fan the alert out to every configured channel; one channel's
+            // failure must not stop the others from firing.
+            info!("Sending digest to {} channel(s)...", notifiers.len());
+            for notifier in notifiers {
+                match notifier.send_alert(&due).await {
+                    Ok(_) => info!("Notifier sent alert successfully!"),
+                    Err(e) => error!("Notifier failed: {} (results printed above)", e),
+                }
+            }
         }
     } else {
         info!("No coins meeting all conditions, skipping notification");
     }
 
-    Ok(matching.len())
+    Ok(matching_count)
 }
 
 async fn analyze_coin(
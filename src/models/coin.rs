@@ -15,7 +15,7 @@ pub struct CoinInfo {
 }
 
 /// 多时间周期布林带数据
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct MultiTimeframeBoll {
     pub boll_15m_upper: f64,
     pub boll_15m_middle: f64,
@@ -26,7 +26,7 @@ pub struct MultiTimeframeBoll {
 }
 
 /// 分析结果
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct AnalyzedCoin {
     pub coin: CoinInfo,
     pub current_price: f64,
@@ -0,0 +1,90 @@
+use crate::error::{AppError, Result};
+use serde_json::Value;
+
+/// 一根现货OHLC (来自 CoinGecko `/coins/{id}/ohlc`, 无成交量)
+#[derive(Debug, Clone)]
+pub struct CoinOhlc {
+    pub timestamp_ms: i64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
+
+impl CoinOhlc {
+    pub fn from_coingecko_response(data: &[Value]) -> Result<Self> {
+        if data.len() < 5 {
+            return Err(AppError::CoinGeckoApi("Invalid OHLC data format".to_string()));
+        }
+
+        let parse_f64 = |v: &Value| -> Result<f64> {
+            v.as_f64()
+                .ok_or_else(|| AppError::CoinGeckoApi("Expected number".to_string()))
+        };
+
+        Ok(CoinOhlc {
+            timestamp_ms: data[0]
+                .as_i64()
+                .ok_or_else(|| AppError::CoinGeckoApi("Invalid timestamp".to_string()))?,
+            open: parse_f64(&data[1])?,
+            high: parse_f64(&data[2])?,
+            low: parse_f64(&data[3])?,
+            close: parse_f64(&data[4])?,
+        })
+    }
+}
+
+/// 单个 `[timestamp_ms, value]` 采样点 (市值图表的三条序列共用这个形状)
+#[derive(Debug, Clone, Copy)]
+pub struct MarketChartPoint {
+    pub timestamp_ms: i64,
+    pub value: f64,
+}
+
+impl MarketChartPoint {
+    fn from_pair(pair: &[f64]) -> Result<Self> {
+        if pair.len() < 2 {
+            return Err(AppError::CoinGeckoApi("Invalid market_chart point".to_string()));
+        }
+        Ok(MarketChartPoint {
+            timestamp_ms: pair[0] as i64,
+            value: pair[1],
+        })
+    }
+}
+
+/// 来自 CoinGecko `/coins/{id}/market_chart` 的价格/市值/成交量时间序列
+#[derive(Debug, Clone, Default)]
+pub struct MarketChart {
+    pub prices: Vec<MarketChartPoint>,
+    pub market_caps: Vec<MarketChartPoint>,
+    pub total_volumes: Vec<MarketChartPoint>,
+}
+
+impl MarketChart {
+    pub fn from_coingecko_response(value: &Value) -> Result<Self> {
+        let series = |key: &str| -> Result<Vec<MarketChartPoint>> {
+            value
+                .get(key)
+                .and_then(Value::as_array)
+                .ok_or_else(|| AppError::CoinGeckoApi(format!("Missing '{key}' in market_chart")))?
+                .iter()
+                .map(|pair| {
+                    let pair: Vec<f64> = pair
+                        .as_array()
+                        .ok_or_else(|| AppError::CoinGeckoApi("Invalid market_chart point".to_string()))?
+                        .iter()
+                        .map(|n| n.as_f64().unwrap_or_default())
+                        .collect();
+                    MarketChartPoint::from_pair(&pair)
+                })
+                .collect()
+        };
+
+        Ok(MarketChart {
+            prices: series("prices")?,
+            market_caps: series("market_caps")?,
+            total_volumes: series("total_volumes")?,
+        })
+    }
+}
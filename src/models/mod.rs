@@ -1,7 +1,11 @@
 mod coin;
+mod coingecko_market;
 mod kline;
 mod open_interest;
+mod ratio;
 
 pub use coin::{AnalyzedCoin, CoinInfo, MultiTimeframeBoll};
+pub use coingecko_market::{CoinOhlc, MarketChart, MarketChartPoint};
 pub use kline::Kline;
 pub use open_interest::{OpenInterest, OpenInterestHist};
+pub use ratio::LongShortRatio;
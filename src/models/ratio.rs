@@ -0,0 +1,16 @@
+use serde::Deserialize;
+
+/// 大户持仓量多空比 (来自 Binance `topLongShortPositionRatio`)
+#[derive(Debug, Clone, Deserialize)]
+pub struct LongShortRatio {
+    pub symbol: String,
+    #[serde(rename = "longShortRatio")]
+    pub long_short_ratio: String,
+    pub timestamp: i64,
+}
+
+impl LongShortRatio {
+    pub fn long_short_ratio_f64(&self) -> f64 {
+        self.long_short_ratio.parse().unwrap_or(0.0)
+    }
+}
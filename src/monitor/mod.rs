@@ -0,0 +1,3 @@
+mod oi_monitor;
+
+pub use oi_monitor::OiMonitor;
@@ -0,0 +1,154 @@
+use crate::api::BinanceClient;
+use crate::config::MonitorConfig;
+use crate::error::Result;
+
+use serde_json::json;
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+/// 持仓量滚动窗口变化的方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OiDirection {
+    Up,
+    Down,
+}
+
+impl OiDirection {
+    fn as_str(&self) -> &'static str {
+        match self {
+            OiDirection::Up => "up",
+            OiDirection::Down => "down",
+        }
+    }
+}
+
+/// 一次持仓量/多空比告警
+struct OiAlert {
+    symbol: String,
+    old_oi: f64,
+    new_oi: f64,
+    pct_change: f64,
+    direction: OiDirection,
+    long_short_ratio: Option<f64>,
+}
+
+impl OiAlert {
+    fn log(&self) {
+        warn!(
+            symbol = %self.symbol,
+            old_oi = self.old_oi,
+            new_oi = self.new_oi,
+            pct_change = self.pct_change,
+            direction = self.direction.as_str(),
+            long_short_ratio = self.long_short_ratio,
+            "OI alert"
+        );
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        json!({
+            "event": "prophet.oi_alert",
+            "symbol": self.symbol,
+            "old_oi": self.old_oi,
+            "new_oi": self.new_oi,
+            "pct_change": self.pct_change,
+            "direction": self.direction.as_str(),
+            "long_short_ratio": self.long_short_ratio,
+        })
+    }
+}
+
+/// 轮询 Binance 持仓量 + 大户多空比, 对watchlist里的每个合约维护一个滚动窗口,
+/// 窗口内持仓量变动超过阈值或多空比越过阈值就发出告警。
+pub struct OiMonitor {
+    binance: BinanceClient,
+    config: MonitorConfig,
+    webhook_client: reqwest::Client,
+    windows: HashMap<String, VecDeque<f64>>,
+}
+
+impl OiMonitor {
+    pub fn new(binance: BinanceClient, config: MonitorConfig) -> Self {
+        Self {
+            binance,
+            config,
+            webhook_client: reqwest::Client::new(),
+            windows: HashMap::new(),
+        }
+    }
+
+    /// 持续轮询直到进程退出, 每轮之间按 `poll_interval_secs` 休眠
+    pub async fn run(&mut self) {
+        info!(
+            symbols = self.config.watchlist.len(),
+            interval_secs = self.config.poll_interval_secs,
+            "OI monitor starting"
+        );
+
+        loop {
+            for symbol in self.config.watchlist.clone() {
+                if let Err(e) = self.poll_symbol(&symbol).await {
+                    error!(symbol = %symbol, error = %e, "OI monitor poll failed");
+                }
+            }
+
+            tokio::time::sleep(Duration::from_secs(self.config.poll_interval_secs)).await;
+        }
+    }
+
+    async fn poll_symbol(&mut self, symbol: &str) -> Result<()> {
+        let (current_oi, long_short_ratio) = self.binance.get_oi_monitor_snapshot(symbol).await?;
+
+        let window = self.windows.entry(symbol.to_string()).or_default();
+        window.push_back(current_oi);
+        while window.len() > self.config.window_size {
+            window.pop_front();
+        }
+
+        if let Some(&oldest_oi) = window.front() {
+            if oldest_oi > 0.0 {
+                let pct_change = (current_oi - oldest_oi) / oldest_oi * 100.0;
+                if pct_change.abs() >= self.config.oi_change_pct_threshold {
+                    let alert = OiAlert {
+                        symbol: symbol.to_string(),
+                        old_oi: oldest_oi,
+                        new_oi: current_oi,
+                        pct_change,
+                        direction: if pct_change >= 0.0 { OiDirection::Up } else { OiDirection::Down },
+                        long_short_ratio,
+                    };
+                    self.fire(alert).await;
+                    return Ok(());
+                }
+            }
+        }
+
+        if let Some(ratio) = long_short_ratio {
+            let threshold = self.config.long_short_ratio_threshold;
+            if ratio >= threshold || ratio <= 1.0 / threshold {
+                let alert = OiAlert {
+                    symbol: symbol.to_string(),
+                    old_oi: current_oi,
+                    new_oi: current_oi,
+                    pct_change: 0.0,
+                    direction: if ratio >= threshold { OiDirection::Up } else { OiDirection::Down },
+                    long_short_ratio: Some(ratio),
+                };
+                self.fire(alert).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn fire(&self, alert: OiAlert) {
+        alert.log();
+
+        if let Some(url) = &self.config.webhook_url {
+            if let Err(e) = self.webhook_client.post(url).json(&alert.to_json()).send().await {
+                error!(symbol = %alert.symbol, error = %e, "OI alert webhook dispatch failed");
+            }
+        }
+    }
+}
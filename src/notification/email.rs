@@ -1,6 +1,7 @@
 use crate::config::EmailConfig;
 use crate::error::{AppError, Result};
 use crate::models::AnalyzedCoin;
+use crate::notification::Notifier;
 
 use chrono::Local;
 use lettre::message::header::ContentType;
@@ -230,3 +231,14 @@ impl EmailNotifier {
         )
     }
 }
+
+#[async_trait::async_trait]
+impl Notifier for EmailNotifier {
+    async fn send_alert(&self, coins: &[&AnalyzedCoin]) -> Result<()> {
+        self.send_alert_v2(coins).await
+    }
+
+    async fn send_heartbeat(&self) -> Result<()> {
+        EmailNotifier::send_heartbeat(self).await
+    }
+}
@@ -0,0 +1,9 @@
+mod email;
+mod notifier;
+mod telegram;
+mod webhook;
+
+pub use email::EmailNotifier;
+pub use notifier::Notifier;
+pub use telegram::TelegramNotifier;
+pub use webhook::WebhookNotifier;
@@ -0,0 +1,12 @@
+use crate::error::Result;
+use crate::models::AnalyzedCoin;
+
+/// One alert delivery channel. Implementations must not let a transient
+/// failure on their side affect any other `Notifier` the runner holds —
+/// callers are expected to invoke each notifier independently and log
+/// per-channel errors rather than short-circuit on the first one.
+#[async_trait::async_trait]
+pub trait Notifier: Send + Sync {
+    async fn send_alert(&self, coins: &[&AnalyzedCoin]) -> Result<()>;
+    async fn send_heartbeat(&self) -> Result<()>;
+}
@@ -0,0 +1,88 @@
+use crate::config::TelegramConfig;
+use crate::error::{AppError, Result};
+use crate::models::AnalyzedCoin;
+use crate::notification::Notifier;
+
+use chrono::Local;
+use serde_json::json;
+
+pub struct TelegramNotifier {
+    config: TelegramConfig,
+    client: reqwest::Client,
+}
+
+impl TelegramNotifier {
+    pub fn new(config: TelegramConfig) -> Self {
+        Self {
+            config,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    async fn send_message(&self, text: String) -> Result<()> {
+        let url = format!(
+            "https://api.telegram.org/bot{}/sendMessage",
+            self.config.bot_token
+        );
+
+        let response = self
+            .client
+            .post(&url)
+            .json(&json!({
+                "chat_id": self.config.chat_id,
+                "text": text,
+                "parse_mode": "Markdown",
+            }))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(AppError::EmailError(format!("Telegram sendMessage failed: {text}")));
+        }
+
+        Ok(())
+    }
+
+    fn build_table(&self, coins: &[&AnalyzedCoin]) -> String {
+        let mut lines = vec![format!(
+            "*Prophet v2*: {} coin(s) meet all 7 conditions",
+            coins.len()
+        )];
+        lines.push("```".to_string());
+        lines.push(format!("{:<10} {:>12} {:>8}", "Symbol", "Price", "OI x"));
+        for coin in coins {
+            let oi_ratio = if coin.min_oi_3d > 0.0 {
+                coin.current_oi / coin.min_oi_3d
+            } else {
+                0.0
+            };
+            lines.push(format!(
+                "{:<10} {:>12.4} {:>8.2}",
+                coin.coin.symbol.to_uppercase(),
+                coin.current_price,
+                oi_ratio
+            ));
+        }
+        lines.push("```".to_string());
+        lines.join("\n")
+    }
+}
+
+#[async_trait::async_trait]
+impl Notifier for TelegramNotifier {
+    async fn send_alert(&self, coins: &[&AnalyzedCoin]) -> Result<()> {
+        if coins.is_empty() {
+            return Ok(());
+        }
+        self.send_message(self.build_table(coins)).await
+    }
+
+    async fn send_heartbeat(&self) -> Result<()> {
+        let text = format!(
+            "Prophet heartbeat — system running normally at {}",
+            Local::now().format("%Y-%m-%d %H:%M:%S")
+        );
+        self.send_message(text).await
+    }
+}
@@ -0,0 +1,76 @@
+use crate::config::WebhookConfig;
+use crate::error::{AppError, Result};
+use crate::models::AnalyzedCoin;
+use crate::notification::Notifier;
+
+use chrono::Local;
+use serde_json::json;
+
+/// Posts alerts as a generic JSON body so users can wire Prophet into
+/// whatever receives webhooks (Slack-compatible endpoints, custom
+/// dashboards, etc.) without Prophet knowing the target's shape.
+pub struct WebhookNotifier {
+    config: WebhookConfig,
+    client: reqwest::Client,
+}
+
+impl WebhookNotifier {
+    pub fn new(config: WebhookConfig) -> Self {
+        Self {
+            config,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    async fn post(&self, payload: serde_json::Value) -> Result<()> {
+        let response = self.client.post(&self.config.url).json(&payload).send().await?;
+
+        if !response.status().is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(AppError::EmailError(format!("webhook POST failed: {text}")));
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Notifier for WebhookNotifier {
+    async fn send_alert(&self, coins: &[&AnalyzedCoin]) -> Result<()> {
+        if coins.is_empty() {
+            return Ok(());
+        }
+
+        let matches: Vec<_> = coins
+            .iter()
+            .map(|coin| {
+                let oi_ratio = if coin.min_oi_3d > 0.0 {
+                    coin.current_oi / coin.min_oi_3d
+                } else {
+                    0.0
+                };
+                json!({
+                    "symbol": coin.coin.symbol.to_uppercase(),
+                    "name": coin.coin.name,
+                    "price": coin.current_price,
+                    "oi_ratio": oi_ratio,
+                })
+            })
+            .collect();
+
+        self.post(json!({
+            "event": "prophet.match",
+            "timestamp": Local::now().to_rfc3339(),
+            "matches": matches,
+        }))
+        .await
+    }
+
+    async fn send_heartbeat(&self) -> Result<()> {
+        self.post(json!({
+            "event": "prophet.heartbeat",
+            "timestamp": Local::now().to_rfc3339(),
+        }))
+        .await
+    }
+}
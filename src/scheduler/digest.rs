@@ -0,0 +1,154 @@
+use crate::config::SchedulerConfig;
+use crate::error::Result;
+use crate::models::AnalyzedCoin;
+
+use chrono::{DateTime, Datelike, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Snapshot of the six condition flags, so a rollover can tell whether a
+/// coin's match actually *changed* shape rather than just fired again with
+/// the same conditions as last time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchState {
+    pub cond1: bool,
+    pub cond2: bool,
+    pub cond3: bool,
+    pub cond4: bool,
+    pub cond5: bool,
+    pub cond6: bool,
+}
+
+impl From<&AnalyzedCoin> for MatchState {
+    fn from(coin: &AnalyzedCoin) -> Self {
+        Self {
+            cond1: coin.cond1_price_above_15m_upper,
+            cond2: coin.cond2_price_above_30m_middle,
+            cond3: coin.cond3_price_above_4h_middle,
+            cond4: coin.cond4_15m_history_below_upper,
+            cond5: coin.cond5_30m_history_below_middle,
+            cond6: coin.cond6_oi_condition,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct FiredRecord {
+    last_fired: DateTime<Utc>,
+    state: MatchState,
+}
+
+/// Anchors alert delivery to fixed UTC instants instead of firing on every
+/// analysis cycle: a digest is due at each hour in `digest_hours_utc`, and
+/// a separate daily summary slot at `daily_summary_hour_utc` (see
+/// [`Self::daily_summary_due`]; no match digest is tied to it). If the process wakes up
+/// mid-window (a missed digest hour), the next poll still fires it once
+/// rather than skipping straight to the following window.
+///
+/// Within a digest, a coin that already fired is suppressed until
+/// `cooldown_minutes` has passed, unless its condition set changed since it
+/// last fired (a "rollover") — that re-notifies immediately. Last-fired
+/// state is persisted to `state_file` so a restart doesn't re-spam coins
+/// that already notified before the process restarted.
+pub struct DigestScheduler {
+    config: SchedulerConfig,
+    state_file: PathBuf,
+    fired: HashMap<String, FiredRecord>,
+    last_hourly_slot: Option<(i32, u32, u32, u32)>,
+    last_daily_slot: Option<(i32, u32, u32)>,
+}
+
+impl DigestScheduler {
+    pub fn new(config: SchedulerConfig) -> Result<Self> {
+        let state_file = PathBuf::from(&config.state_file);
+        let fired = Self::load(&state_file)?;
+
+        Ok(Self {
+            config,
+            state_file,
+            fired,
+            last_hourly_slot: None,
+            last_daily_slot: None,
+        })
+    }
+
+    fn load(path: &Path) -> Result<HashMap<String, FiredRecord>> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Ok(serde_json::from_str(&contents).unwrap_or_default()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(HashMap::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn save(&self) -> Result<()> {
+        if let Some(parent) = self.state_file.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+        let contents = serde_json::to_string_pretty(&self.fired)?;
+        fs::write(&self.state_file, contents)?;
+        Ok(())
+    }
+
+    /// Whether an hourly digest window has newly elapsed since the last
+    /// time this was checked (fires the missed window once if we woke up
+    /// mid-window rather than exactly on the hour).
+    pub fn hourly_digest_due(&mut self, now: DateTime<Utc>) -> bool {
+        if !self.config.digest_hours_utc.contains(&now.hour()) {
+            return false;
+        }
+        let slot = (now.year(), now.ordinal(), now.hour(), 0);
+        if self.last_hourly_slot == Some(slot) {
+            return false;
+        }
+        self.last_hourly_slot = Some(slot);
+        true
+    }
+
+    /// Same idea as [`Self::hourly_digest_due`] but for the single daily
+    /// summary slot.
+    pub fn daily_summary_due(&mut self, now: DateTime<Utc>) -> bool {
+        if now.hour() != self.config.daily_summary_hour_utc {
+            return false;
+        }
+        let slot = (now.year(), now.ordinal(), now.hour());
+        if self.last_daily_slot == Some(slot) {
+            return false;
+        }
+        self.last_daily_slot = Some(slot);
+        true
+    }
+
+    /// Filters `coins` (already known to meet all conditions) down to the
+    /// ones that should actually be notified right now: never seen before,
+    /// past cooldown, or rolled over onto a different condition set.
+    /// Updates and persists fired state for every coin returned.
+    pub fn filter_due(&mut self, coins: &[&AnalyzedCoin], now: DateTime<Utc>) -> Result<Vec<String>> {
+        let cooldown = chrono::Duration::minutes(self.config.cooldown_minutes);
+        let mut due = Vec::new();
+
+        for coin in coins {
+            let symbol = coin.coin.symbol.to_uppercase();
+            let state = MatchState::from(*coin);
+
+            let should_fire = match self.fired.get(&symbol) {
+                None => true,
+                Some(record) => record.state != state || now - record.last_fired >= cooldown,
+            };
+
+            if should_fire {
+                self.fired.insert(symbol.clone(), FiredRecord { last_fired: now, state });
+                due.push(symbol);
+            }
+        }
+
+        if !due.is_empty() {
+            self.save()?;
+        }
+
+        Ok(due)
+    }
+}
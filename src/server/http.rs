@@ -0,0 +1,45 @@
+use crate::error::{AppError, Result};
+use crate::server::signal_store::SignalStore;
+
+use axum::extract::{Path as AxumPath, State};
+use axum::http::StatusCode;
+use axum::response::Json;
+use axum::routing::get;
+use axum::Router;
+use std::sync::Arc;
+use tracing::info;
+
+/// Serves the latest scan results over HTTP so dashboards and other
+/// services can read them directly instead of scraping email/webhook
+/// output. Purely a read-only view over `SignalStore`; it doesn't
+/// participate in analysis or notification in any way.
+pub async fn serve(bind_addr: &str, store: Arc<SignalStore>) -> Result<()> {
+    let app = Router::new()
+        .route("/signals", get(list_signals))
+        .route("/signals/:symbol", get(symbol_history))
+        .with_state(store);
+
+    let listener = tokio::net::TcpListener::bind(bind_addr)
+        .await
+        .map_err(AppError::Io)?;
+    info!(bind_addr, "signal HTTP API listening");
+
+    axum::serve(listener, app)
+        .await
+        .map_err(|e| AppError::StreamError(format!("HTTP server error: {e}")))
+}
+
+async fn list_signals(State(store): State<Arc<SignalStore>>) -> Json<serde_json::Value> {
+    Json(serde_json::json!(store.latest()))
+}
+
+async fn symbol_history(
+    State(store): State<Arc<SignalStore>>,
+    AxumPath(symbol): AxumPath<String>,
+) -> std::result::Result<Json<serde_json::Value>, StatusCode> {
+    let history = store.history(&symbol);
+    if history.is_empty() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    Ok(Json(serde_json::json!(history)))
+}
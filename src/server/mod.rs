@@ -0,0 +1,5 @@
+mod http;
+mod signal_store;
+
+pub use http::serve;
+pub use signal_store::SignalStore;
@@ -0,0 +1,96 @@
+use crate::events::{EventBus, MatchEvent};
+use crate::models::AnalyzedCoin;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, RwLock};
+use tracing::warn;
+
+/// How many past matches to keep per symbol for `/signals/{symbol}`; older
+/// entries are dropped once a symbol's queue fills up.
+const HISTORY_PER_SYMBOL: usize = 50;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StoredSignal {
+    pub coin: AnalyzedCoin,
+    pub fired_at: DateTime<Utc>,
+}
+
+impl From<&MatchEvent> for StoredSignal {
+    fn from(event: &MatchEvent) -> Self {
+        Self {
+            coin: event.coin.clone(),
+            fired_at: event.fired_at,
+        }
+    }
+}
+
+/// In-memory view of every coin that has met all conditions, keyed by
+/// symbol, so the HTTP server has something to read without touching the
+/// CSV/Postgres/email paths the scanner already writes to. Subscribes to
+/// the `EventBus` the same way `AuditLogWriter` does; falling behind only
+/// drops entries from this cache, it never blocks analysis.
+#[derive(Default)]
+pub struct SignalStore {
+    by_symbol: RwLock<HashMap<String, VecDeque<StoredSignal>>>,
+}
+
+impl SignalStore {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    fn record(&self, event: &MatchEvent) {
+        let symbol = event.coin.coin.symbol.to_uppercase();
+        let mut by_symbol = self.by_symbol.write().unwrap();
+        let history = by_symbol.entry(symbol).or_default();
+        history.push_back(StoredSignal::from(event));
+        while history.len() > HISTORY_PER_SYMBOL {
+            history.pop_front();
+        }
+    }
+
+    /// Every symbol's most recently fired signal.
+    pub fn latest(&self) -> Vec<StoredSignal> {
+        self.by_symbol
+            .read()
+            .unwrap()
+            .values()
+            .filter_map(|history| history.back().cloned())
+            .collect()
+    }
+
+    /// The full retained history for one symbol, oldest first.
+    pub fn history(&self, symbol: &str) -> Vec<StoredSignal> {
+        self.by_symbol
+            .read()
+            .unwrap()
+            .get(&symbol.to_uppercase())
+            .map(|history| history.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Spawns the background task that keeps this store in sync with the
+    /// `EventBus`. Returns the store itself so the caller can hand the same
+    /// `Arc` to the HTTP server.
+    pub fn spawn(bus: &EventBus) -> Arc<Self> {
+        let store = Self::new();
+        let mut receiver = bus.subscribe();
+        let task_store = store.clone();
+
+        tokio::spawn(async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => task_store.record(&event),
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("Signal store lagged, dropped {} match event(s)", skipped);
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+
+        store
+    }
+}
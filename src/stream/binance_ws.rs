@@ -0,0 +1,214 @@
+use crate::api::BinanceClient;
+use crate::error::{AppError, Result};
+use crate::models::Kline;
+
+use futures_util::{SinkExt, StreamExt};
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio_tungstenite::tungstenite::Message;
+use tracing::{debug, info, warn};
+
+const WS_BASE_URL: &str = "wss://fstream.binance.com/stream";
+const BACKOFF_INITIAL: Duration = Duration::from_secs(1);
+const BACKOFF_CAP: Duration = Duration::from_secs(30);
+// Binance force-closes futures streams after 24h; reconnect a bit early.
+const MAX_CONNECTION_AGE: Duration = Duration::from_secs(23 * 60 * 60);
+// `/fapi/v1/openInterest` isn't pushed over the websocket (the `@markPrice`
+// frame only ever carries mark/index price and funding), so open interest
+// is kept fresh by polling the REST endpoint on this cadence instead.
+const OI_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// A live view of one symbol's klines (at a caller-chosen interval) and
+/// its open interest, fed by a background task that owns the websocket
+/// connection plus a separate task that polls `/fapi/v1/openInterest`.
+/// Consumers read the latest value with `kline()`/`open_interest()` or
+/// `.changed().await` on the underlying receivers to wake on updates.
+pub struct SymbolStream {
+    kline_rx: watch::Receiver<Kline>,
+    open_interest_rx: watch::Receiver<f64>,
+}
+
+impl SymbolStream {
+    /// Fetches `interval` candles over REST and seeds the watch channel with
+    /// the `history_len`-th most recent one (the latest when `history_len`
+    /// is 1), so `kline()` has a value before the first websocket frame.
+    /// The rest of the fetched series is not kept, and nothing is fetched
+    /// again on reconnect. Then hands off to a background task that
+    /// maintains the live websocket connection for `symbol` at that same
+    /// interval.
+    pub async fn connect(
+        binance: Arc<BinanceClient>,
+        symbol: String,
+        interval: String,
+        history_len: usize,
+    ) -> Result<Self> {
+        let backfill = binance.get_futures_klines(&symbol, &interval).await?;
+        let seed = backfill
+            .into_iter()
+            .rev()
+            .take(history_len)
+            .last()
+            .ok_or_else(|| AppError::StreamError(format!("no backfill data for {symbol}")))?;
+
+        let (kline_tx, kline_rx) = watch::channel(seed);
+        let (oi_tx, open_interest_rx) = watch::channel(0.0);
+
+        tokio::spawn(run_connection(symbol.clone(), interval, kline_tx));
+        tokio::spawn(poll_open_interest(binance, symbol, oi_tx));
+
+        Ok(Self {
+            kline_rx,
+            open_interest_rx,
+        })
+    }
+
+    pub fn kline(&self) -> Kline {
+        self.kline_rx.borrow().clone()
+    }
+
+    pub fn open_interest(&self) -> f64 {
+        *self.open_interest_rx.borrow()
+    }
+
+    pub fn kline_watch(&self) -> watch::Receiver<Kline> {
+        self.kline_rx.clone()
+    }
+
+    pub fn open_interest_watch(&self) -> watch::Receiver<f64> {
+        self.open_interest_rx.clone()
+    }
+}
+
+/// Owns the reconnect loop: runs `connect_and_stream` until it returns,
+/// then backs off with exponential delay (capped) before retrying.
+async fn run_connection(symbol: String, interval: String, kline_tx: watch::Sender<Kline>) {
+    let mut backoff = BACKOFF_INITIAL;
+    loop {
+        match connect_and_stream(&symbol, &interval, &kline_tx).await {
+            Ok(()) => info!(symbol, "stream closed cleanly, reconnecting"),
+            Err(e) => warn!(symbol, error = %e, "stream error, reconnecting"),
+        }
+
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(BACKOFF_CAP);
+    }
+}
+
+/// Polls `/fapi/v1/openInterest` on `OI_POLL_INTERVAL` and publishes each
+/// reading, retrying indefinitely on request failure after a fixed
+/// `BACKOFF_INITIAL` delay (unlike the websocket reconnect, the delay does
+/// not grow).
+async fn poll_open_interest(binance: Arc<BinanceClient>, symbol: String, oi_tx: watch::Sender<f64>) {
+    loop {
+        match binance.get_open_interest(&symbol).await {
+            Ok(oi) => {
+                let _ = oi_tx.send(oi.open_interest_f64());
+                tokio::time::sleep(OI_POLL_INTERVAL).await;
+            }
+            Err(e) => {
+                warn!(symbol, error = %e, "open interest poll failed, retrying");
+                tokio::time::sleep(BACKOFF_INITIAL).await;
+            }
+        }
+    }
+}
+
+async fn connect_and_stream(
+    symbol: &str,
+    interval: &str,
+    kline_tx: &watch::Sender<Kline>,
+) -> Result<()> {
+    let lower = symbol.to_lowercase();
+    let url = format!("{WS_BASE_URL}?streams={lower}@kline_{interval}");
+
+    let (mut ws, _) = tokio_tungstenite::connect_async(&url)
+        .await
+        .map_err(|e| AppError::StreamError(format!("connect failed: {e}")))?;
+
+    info!(symbol, "websocket connected");
+    let deadline = tokio::time::Instant::now() + MAX_CONNECTION_AGE;
+    let mut last_open_time: Option<i64> = None;
+
+    loop {
+        let msg = tokio::select! {
+            _ = tokio::time::sleep_until(deadline) => {
+                info!(symbol, "proactively reconnecting before 24h connection cap");
+                let _ = ws.close(None).await;
+                return Ok(());
+            }
+            msg = ws.next() => msg,
+        };
+
+        let msg = match msg {
+            Some(Ok(m)) => m,
+            Some(Err(e)) => {
+                return Err(AppError::StreamError(format!("websocket read failed: {e}")));
+            }
+            None => return Ok(()),
+        };
+
+        match msg {
+            Message::Ping(payload) => {
+                ws.send(Message::Pong(payload))
+                    .await
+                    .map_err(|e| AppError::StreamError(format!("pong failed: {e}")))?;
+            }
+            Message::Close(_) => return Ok(()),
+            Message::Text(text) => {
+                handle_frame(&text, &mut last_open_time, kline_tx);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn handle_frame(text: &str, last_open_time: &mut Option<i64>, kline_tx: &watch::Sender<Kline>) {
+    let envelope: Value = match serde_json::from_str(text) {
+        Ok(v) => v,
+        Err(e) => {
+            debug!(error = %e, "failed to parse stream frame, skipping");
+            return;
+        }
+    };
+
+    let stream = envelope["stream"].as_str().unwrap_or_default();
+    let data = &envelope["data"];
+
+    if !stream.contains("@kline_") {
+        return;
+    }
+
+    let k = &data["k"];
+    let is_closed = k["x"].as_bool().unwrap_or(false);
+    if !is_closed {
+        return;
+    }
+
+    let Some(open_time) = k["t"].as_i64() else {
+        return;
+    };
+
+    // Only a closed candle we haven't already applied should update the window.
+    if *last_open_time == Some(open_time) {
+        return;
+    }
+
+    let kline = Kline {
+        open_time,
+        open: parse_f64(&k["o"]),
+        high: parse_f64(&k["h"]),
+        low: parse_f64(&k["l"]),
+        close: parse_f64(&k["c"]),
+        volume: parse_f64(&k["v"]),
+        close_time: k["T"].as_i64().unwrap_or(open_time),
+    };
+
+    *last_open_time = Some(open_time);
+    let _ = kline_tx.send(kline);
+}
+
+fn parse_f64(v: &Value) -> f64 {
+    v.as_str().and_then(|s| s.parse().ok()).unwrap_or(0.0)
+}
@@ -0,0 +1,3 @@
+mod binance_ws;
+
+pub use binance_ws::SymbolStream;
@@ -0,0 +1,301 @@
+use anyhow::{Context, Result};
+use clap::Parser;
+use futures::{stream, StreamExt};
+use serde::Deserialize;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+/// 对 klines/<symbol>/<interval>/*.csv 跑一个可插拔的动量策略回测
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// K线根目录 (downloader 产出的 klines/ 目录)
+    #[arg(short = 'd', long, default_value = "data/klines")]
+    klines_dir: String,
+
+    /// 回测使用的K线周期 (需与下载时的目录名一致)
+    #[arg(short, long, default_value = "15m")]
+    interval: String,
+
+    /// 信号阈值: 收盘相对开盘的涨幅百分比, 达到即视为动量信号
+    #[arg(short, long, default_value_t = 1.0)]
+    threshold: f64,
+
+    /// 单笔交易的手续费+滑点 (占名义金额的百分比), 从收益中扣除
+    #[arg(long, default_value_t = 0.08)]
+    fee_pct: f64,
+
+    /// 并发回测的交易对数
+    #[arg(short, long, default_value_t = 16)]
+    concurrent: usize,
+
+    /// 把每笔交易明细写到这个 CSV (不指定则只输出汇总表)
+    #[arg(long)]
+    trades_csv: Option<String>,
+}
+
+/// Binance 合约K线 CSV 的列布局 (无表头):
+/// open_time, open, high, low, close, volume, close_time, quote_volume,
+/// count, taker_buy_base, taker_buy_quote, ignore
+#[derive(Debug, Clone, Deserialize)]
+#[allow(dead_code)]
+struct KlineRow {
+    open_time: i64,
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+    volume: f64,
+    close_time: i64,
+    quote_volume: f64,
+    count: u64,
+    taker_buy_base: f64,
+    taker_buy_quote: f64,
+    ignore: i64,
+}
+
+/// 一笔已平仓交易
+struct Trade {
+    symbol: String,
+    signal_open_time: i64,
+    entry_price: f64,
+    exit_price: f64,
+    /// 扣除手续费/滑点后的收益率
+    pnl_pct: f64,
+}
+
+/// 单个交易对的回测结果
+struct SymbolReport {
+    symbol: String,
+    trades: Vec<Trade>,
+}
+
+impl SymbolReport {
+    fn total_return(&self) -> f64 {
+        self.trades.iter().map(|t| t.pnl_pct).sum()
+    }
+
+    fn average_return(&self) -> f64 {
+        if self.trades.is_empty() {
+            0.0
+        } else {
+            self.total_return() / self.trades.len() as f64
+        }
+    }
+
+    fn win_rate(&self) -> f64 {
+        if self.trades.is_empty() {
+            return 0.0;
+        }
+        let wins = self.trades.iter().filter(|t| t.pnl_pct > 0.0).count();
+        wins as f64 / self.trades.len() as f64
+    }
+
+    /// 按交易顺序把收益率当复利处理, 计算权益曲线的最大回撤
+    fn max_drawdown(&self) -> f64 {
+        let mut equity = 1.0;
+        let mut peak = 1.0;
+        let mut max_dd = 0.0;
+
+        for trade in &self.trades {
+            equity *= 1.0 + trade.pnl_pct / 100.0;
+            peak = peak.max(equity);
+            let dd = (peak - equity) / peak;
+            max_dd = max_dd.max(dd);
+        }
+
+        max_dd * 100.0
+    }
+}
+
+/// 读取一个交易对在给定周期下的全部K线, 按文件名 (年月) 顺序拼接
+fn load_klines(klines_dir: &Path, symbol: &str, interval: &str) -> Result<Vec<KlineRow>> {
+    let dir = klines_dir.join(symbol).join(interval);
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut files: Vec<PathBuf> = fs::read_dir(&dir)
+        .with_context(|| format!("无法读取目录: {}", dir.display()))?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("csv"))
+        .collect();
+    files.sort();
+
+    let mut rows = Vec::new();
+    for path in files {
+        let file = File::open(&path).with_context(|| format!("无法打开: {}", path.display()))?;
+        let mut reader = csv::ReaderBuilder::new().has_headers(false).from_reader(file);
+        for record in reader.deserialize() {
+            let row: KlineRow = record.with_context(|| format!("解析失败: {}", path.display()))?;
+            rows.push(row);
+        }
+    }
+
+    rows.sort_by_key(|r| r.open_time);
+    Ok(rows)
+}
+
+/// 参考动量策略: 收盘相对开盘涨幅达到 threshold% 的K线视为信号,
+/// 下一根K线开盘做多进场, 再下一根K线收盘平仓。
+fn run_strategy(symbol: &str, klines: &[KlineRow], threshold_pct: f64, fee_pct: f64) -> SymbolReport {
+    let mut trades = Vec::new();
+
+    if klines.len() < 3 {
+        return SymbolReport { symbol: symbol.to_string(), trades };
+    }
+
+    for i in 0..klines.len() - 2 {
+        let signal = &klines[i];
+        let move_pct = (signal.close - signal.open) / signal.open * 100.0;
+        if move_pct < threshold_pct {
+            continue;
+        }
+
+        let entry = &klines[i + 1];
+        let exit = &klines[i + 2];
+
+        let raw_return_pct = (exit.close - entry.open) / entry.open * 100.0;
+        let pnl_pct = raw_return_pct - fee_pct;
+
+        trades.push(Trade {
+            symbol: symbol.to_string(),
+            signal_open_time: signal.open_time,
+            entry_price: entry.open,
+            exit_price: exit.close,
+            pnl_pct,
+        });
+    }
+
+    SymbolReport { symbol: symbol.to_string(), trades }
+}
+
+fn discover_symbols(klines_dir: &Path) -> Result<Vec<String>> {
+    if !klines_dir.is_dir() {
+        anyhow::bail!("K线目录不存在: {}", klines_dir.display());
+    }
+
+    let mut symbols: Vec<String> = fs::read_dir(klines_dir)
+        .with_context(|| format!("无法读取目录: {}", klines_dir.display()))?
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_dir())
+        .filter_map(|e| e.file_name().into_string().ok())
+        .collect();
+    symbols.sort();
+    Ok(symbols)
+}
+
+fn write_trades_csv(path: &str, reports: &[SymbolReport]) -> Result<()> {
+    let mut writer = csv::Writer::from_path(path).with_context(|| format!("无法创建: {path}"))?;
+    writer.write_record(["symbol", "signal_open_time", "entry_price", "exit_price", "pnl_pct"])?;
+
+    for report in reports {
+        for trade in &report.trades {
+            writer.write_record([
+                trade.symbol.clone(),
+                trade.signal_open_time.to_string(),
+                trade.entry_price.to_string(),
+                trade.exit_price.to_string(),
+                format!("{:.6}", trade.pnl_pct),
+            ])?;
+        }
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+    let klines_dir = PathBuf::from(&args.klines_dir);
+
+    println!("📊 动量策略回测");
+    println!(
+        "   周期: {} | 阈值: {:.2}% | 手续费/滑点: {:.3}%",
+        args.interval, args.threshold, args.fee_pct
+    );
+
+    let symbols = discover_symbols(&klines_dir)?;
+    if symbols.is_empty() {
+        println!("⚠️  没有找到任何交易对");
+        return Ok(());
+    }
+    println!("📋 待回测交易对: {}", symbols.len());
+
+    let interval = args.interval.clone();
+    let threshold = args.threshold;
+    let fee_pct = args.fee_pct;
+
+    let reports: Vec<SymbolReport> = stream::iter(symbols)
+        .map(|symbol| {
+            let klines_dir = klines_dir.clone();
+            let interval = interval.clone();
+
+            async move {
+                tokio::task::spawn_blocking(move || {
+                    let klines = load_klines(&klines_dir, &symbol, &interval).unwrap_or_default();
+                    run_strategy(&symbol, &klines, threshold, fee_pct)
+                })
+                .await
+                .unwrap_or_else(|_| SymbolReport { symbol, trades: Vec::new() })
+            }
+        })
+        .buffer_unordered(args.concurrent)
+        .collect()
+        .await;
+
+    println!("\n{}", "═".repeat(88));
+    println!(
+        "{:<12} {:>8} {:>12} {:>12} {:>10} {:>12}",
+        "Symbol", "Trades", "Total%", "Avg%", "WinRate%", "MaxDD%"
+    );
+    println!("{}", "─".repeat(88));
+
+    let mut total_trades = 0usize;
+    let mut total_return = 0.0;
+    let mut total_wins = 0usize;
+
+    for report in &reports {
+        if report.trades.is_empty() {
+            continue;
+        }
+
+        println!(
+            "{:<12} {:>8} {:>12.3} {:>12.3} {:>10.1} {:>12.3}",
+            report.symbol,
+            report.trades.len(),
+            report.total_return(),
+            report.average_return(),
+            report.win_rate() * 100.0,
+            report.max_drawdown()
+        );
+
+        total_trades += report.trades.len();
+        total_return += report.total_return();
+        total_wins += report.trades.iter().filter(|t| t.pnl_pct > 0.0).count();
+    }
+
+    println!("{}", "─".repeat(88));
+    if total_trades > 0 {
+        println!(
+            "{:<12} {:>8} {:>12.3} {:>12.3} {:>10.1}",
+            "ALL",
+            total_trades,
+            total_return,
+            total_return / total_trades as f64,
+            total_wins as f64 / total_trades as f64 * 100.0
+        );
+    } else {
+        println!("没有产生任何交易 (没有K线触发阈值, 或数据不足)");
+    }
+    println!("{}", "═".repeat(88));
+
+    if let Some(path) = &args.trades_csv {
+        write_trades_csv(path, &reports)?;
+        println!("\n📁 逐笔交易明细已写入: {path}");
+    }
+
+    Ok(())
+}
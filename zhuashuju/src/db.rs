@@ -0,0 +1,156 @@
+use anyhow::{Context, Result};
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use rusqlite::params;
+use serde::Deserialize;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Connection pool shared via `Arc` across the concurrent download workers,
+/// so ingestion doesn't open (and re-migrate) a connection per file.
+pub type DbPool = Arc<Pool<SqliteConnectionManager>>;
+
+/// Binance kline CSV columns (no header row), same layout the backtester reads.
+#[derive(Debug, Deserialize)]
+struct KlineCsvRow {
+    open_time: i64,
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+    volume: f64,
+    #[allow(dead_code)]
+    close_time: i64,
+    quote_volume: f64,
+    count: i64,
+    #[allow(dead_code)]
+    taker_buy_base: f64,
+    #[allow(dead_code)]
+    taker_buy_quote: f64,
+    #[allow(dead_code)]
+    ignore: i64,
+}
+
+/// Columns we keep from Binance's metrics CSV (has a header row; extra
+/// columns such as the top-trader ratios are ignored by serde).
+#[derive(Debug, Deserialize)]
+struct MetricsCsvRow {
+    create_time: String,
+    sum_open_interest: f64,
+    sum_open_interest_value: f64,
+    count_long_short_ratio: f64,
+    sum_taker_long_short_vol_ratio: f64,
+}
+
+/// Opens (creating if needed) the SQLite database at `url` and ensures the
+/// normalized `candles`/`metrics` tables and their unique keys exist.
+pub fn open(url: &str) -> Result<DbPool> {
+    let manager = SqliteConnectionManager::file(url);
+    let pool = Pool::new(manager).context("创建数据库连接池失败")?;
+
+    let conn = pool.get().context("获取数据库连接失败")?;
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS candles (
+            symbol TEXT NOT NULL,
+            interval TEXT NOT NULL,
+            open_time INTEGER NOT NULL,
+            open REAL NOT NULL,
+            high REAL NOT NULL,
+            low REAL NOT NULL,
+            close REAL NOT NULL,
+            volume REAL NOT NULL,
+            quote_volume REAL NOT NULL,
+            trades INTEGER NOT NULL,
+            UNIQUE(symbol, interval, open_time)
+        );
+        CREATE TABLE IF NOT EXISTS metrics (
+            symbol TEXT NOT NULL,
+            create_time TEXT NOT NULL,
+            sum_open_interest REAL NOT NULL,
+            sum_open_interest_value REAL NOT NULL,
+            count_long_short_ratio REAL NOT NULL,
+            sum_taker_long_short_vol_ratio REAL NOT NULL,
+            UNIQUE(symbol, create_time)
+        );
+        ",
+    )
+    .context("创建数据库表失败")?;
+
+    Ok(Arc::new(pool))
+}
+
+/// Parses a just-extracted kline CSV and upserts its rows into `candles`,
+/// inside one transaction. Already-present `(symbol, interval, open_time)`
+/// keys are skipped, so re-running ingestion on the same file is a no-op.
+pub fn ingest_candles(pool: &DbPool, symbol: &str, interval: &str, path: &Path) -> Result<usize> {
+    let mut conn = pool.get().context("获取数据库连接失败")?;
+    let file = std::fs::File::open(path).with_context(|| format!("无法打开: {}", path.display()))?;
+    let mut reader = csv::ReaderBuilder::new().has_headers(false).from_reader(file);
+
+    let tx = conn.transaction()?;
+    let mut inserted = 0;
+    {
+        let mut stmt = tx.prepare(
+            "INSERT INTO candles
+                (symbol, interval, open_time, open, high, low, close, volume, quote_volume, trades)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+             ON CONFLICT(symbol, interval, open_time) DO NOTHING",
+        )?;
+
+        for record in reader.deserialize() {
+            let row: KlineCsvRow = record.with_context(|| format!("解析失败: {}", path.display()))?;
+            let changed = stmt.execute(params![
+                symbol,
+                interval,
+                row.open_time,
+                row.open,
+                row.high,
+                row.low,
+                row.close,
+                row.volume,
+                row.quote_volume,
+                row.count,
+            ])?;
+            inserted += changed;
+        }
+    }
+    tx.commit()?;
+
+    Ok(inserted)
+}
+
+/// Same idea as [`ingest_candles`] but for a daily metrics/open-interest CSV.
+pub fn ingest_metrics(pool: &DbPool, symbol: &str, path: &Path) -> Result<usize> {
+    let mut conn = pool.get().context("获取数据库连接失败")?;
+    let file = std::fs::File::open(path).with_context(|| format!("无法打开: {}", path.display()))?;
+    let mut reader = csv::ReaderBuilder::new().has_headers(true).from_reader(file);
+
+    let tx = conn.transaction()?;
+    let mut inserted = 0;
+    {
+        let mut stmt = tx.prepare(
+            "INSERT INTO metrics
+                (symbol, create_time, sum_open_interest, sum_open_interest_value,
+                 count_long_short_ratio, sum_taker_long_short_vol_ratio)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+             ON CONFLICT(symbol, create_time) DO NOTHING",
+        )?;
+
+        for record in reader.deserialize() {
+            let row: MetricsCsvRow = record.with_context(|| format!("解析失败: {}", path.display()))?;
+            let changed = stmt.execute(params![
+                symbol,
+                row.create_time,
+                row.sum_open_interest,
+                row.sum_open_interest_value,
+                row.count_long_short_ratio,
+                row.sum_taker_long_short_vol_ratio,
+            ])?;
+            inserted += changed;
+        }
+    }
+    tx.commit()?;
+
+    Ok(inserted)
+}
@@ -1,10 +1,16 @@
+mod db;
+mod resume;
+
 use anyhow::{Context, Result};
 use chrono::{Datelike, Duration, NaiveDate, Utc};
 use clap::Parser;
+use db::DbPool;
 use futures::{stream, StreamExt};
 use indicatif::{ProgressBar, ProgressStyle};
+use rand::Rng;
 use reqwest::Client;
 use serde::Deserialize;
+use sha2::{Digest, Sha256};
 use std::fs::{self, File};
 use std::io::{Cursor, Read, Write};
 use std::path::PathBuf;
@@ -12,6 +18,11 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration as StdDuration;
 
+/// 重试退避的初始等待时间
+const RETRY_BACKOFF_INITIAL: StdDuration = StdDuration::from_millis(500);
+/// 重试退避的上限, 避免指数增长失控
+const RETRY_BACKOFF_CAP: StdDuration = StdDuration::from_secs(20);
+
 /// Binance 合约历史数据下载器 (K线 + 持仓量)
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -43,6 +54,23 @@ struct Args {
     /// 只下载持仓量 (不下载K线)
     #[arg(long)]
     oi_only: bool,
+
+    /// 下载后把解析出的K线/持仓量写入这个 SQLite 数据库 (不指定则只写 CSV)
+    #[arg(long)]
+    db: Option<String>,
+
+    /// 下载/校验失败时的最大重试次数 (指数退避 + 抖动)
+    #[arg(long, default_value_t = 3)]
+    max_retries: u32,
+
+    /// 币种范围的来源: Binance 24h成交额排名, 或 CoinGecko 市值排名
+    #[arg(long, value_enum, default_value = "binance-volume")]
+    source: Source,
+
+    /// 跳过归档后的增量补齐 (默认会在归档下载完后, 从每个文件最新的
+    /// close_time/create_time 开始用 REST 接口补到现在)
+    #[arg(long)]
+    no_resume: bool,
 }
 
 /// Binance 24小时行情数据
@@ -54,6 +82,35 @@ struct Ticker24h {
     quote_volume: String,
 }
 
+/// 币种范围的来源
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum Source {
+    /// Binance 24h 合约成交额排名 (默认, 原有行为)
+    BinanceVolume,
+    /// CoinGecko 市值排名, 再交叉校验 Binance 是否有对应的 USDT 永续合约
+    CoingeckoMcap,
+}
+
+/// CoinGecko `/coins/markets` 返回的字段 (只取排名用得到的部分)
+#[derive(Debug, Deserialize)]
+struct CoinGeckoCoin {
+    symbol: String,
+    market_cap: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct BinanceExchangeInfo {
+    symbols: Vec<BinanceSymbolInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BinanceSymbolInfo {
+    symbol: String,
+    #[serde(rename = "contractType")]
+    contract_type: String,
+    status: String,
+}
+
 /// 合约K线时间周期
 const KLINE_INTERVALS: [&str; 3] = ["15m", "30m", "4h"];
 
@@ -113,6 +170,19 @@ impl DownloadTask {
     }
 }
 
+/// 把刚解压出的 CSV 解析并 upsert 进数据库 (幂等: 重复 ingest 同一份文件不会重复插入)
+fn ingest(pool: &DbPool, task: &DownloadTask, csv_path: &std::path::Path) -> Result<()> {
+    match task {
+        DownloadTask::Kline { symbol, interval, .. } => {
+            db::ingest_candles(pool, symbol, interval, csv_path)?;
+        }
+        DownloadTask::Metrics { symbol, .. } => {
+            db::ingest_metrics(pool, symbol, csv_path)?;
+        }
+    }
+    Ok(())
+}
+
 /// 创建优化的 HTTP 客户端
 fn create_optimized_client() -> Result<Client> {
     Client::builder()
@@ -174,6 +244,90 @@ async fn get_futures_symbols(client: &Client, top_n: usize) -> Result<Vec<String
     Ok(symbols)
 }
 
+/// 获取 Binance 所有在线的 USDT 永续合约, 用于交叉校验 CoinGecko 币种是否可下载
+async fn fetch_perpetual_symbols(client: &Client) -> Result<std::collections::HashSet<String>> {
+    let url = "https://fapi.binance.com/fapi/v1/exchangeInfo";
+    let info: BinanceExchangeInfo = client
+        .get(url)
+        .send()
+        .await
+        .context("请求 Binance exchangeInfo 失败")?
+        .json()
+        .await
+        .context("解析 exchangeInfo 失败")?;
+
+    Ok(info
+        .symbols
+        .into_iter()
+        .filter(|s| s.contract_type == "PERPETUAL" && s.status == "TRADING" && s.symbol.ends_with("USDT"))
+        .map(|s| s.symbol)
+        .collect())
+}
+
+/// 按 CoinGecko 市值排名取前 `top_n` 个币种, 映射为 Binance USDT 永续合约符号,
+/// 并跳过 Binance 上没有对应永续合约的币种
+async fn get_coingecko_symbols(client: &Client, top_n: usize) -> Result<Vec<String>> {
+    println!("📊 正在按 CoinGecko 市值排名获取前 {} 个币种...", top_n);
+
+    let perpetuals = fetch_perpetual_symbols(client).await?;
+
+    let mut ranked = Vec::new();
+    let per_page = 250usize;
+    let mut page = 1u32;
+
+    while ranked.len() < top_n {
+        let url = format!(
+            "https://api.coingecko.com/api/v3/coins/markets?vs_currency=usd&order=market_cap_desc&per_page={}&page={}",
+            per_page, page
+        );
+
+        let coins: Vec<CoinGeckoCoin> = client
+            .get(&url)
+            .send()
+            .await
+            .context("请求 CoinGecko API 失败")?
+            .json()
+            .await
+            .context("解析 CoinGecko 响应失败")?;
+
+        if coins.is_empty() {
+            break;
+        }
+        ranked.extend(coins);
+        page += 1;
+
+        // CoinGecko 免费层: 30 req/min
+        tokio::time::sleep(StdDuration::from_secs(2)).await;
+    }
+
+    let mut symbols = Vec::new();
+    let mut skipped = 0usize;
+    for coin in ranked {
+        let binance_symbol = format!("{}USDT", coin.symbol.to_uppercase());
+        if perpetuals.contains(&binance_symbol) {
+            symbols.push(binance_symbol);
+        } else {
+            skipped += 1;
+        }
+        if symbols.len() >= top_n {
+            break;
+        }
+    }
+
+    println!(
+        "✅ 获取到 {} 个合约 (跳过 {} 个无 USDT 永续合约的币种)",
+        symbols.len(),
+        skipped
+    );
+
+    println!("📈 市值前10:");
+    for (i, s) in symbols.iter().take(10).enumerate() {
+        println!("   {}. {}", i + 1, s);
+    }
+
+    Ok(symbols)
+}
+
 /// 生成月份列表 (用于K线)
 fn generate_months(start: Option<&String>, end: Option<&String>) -> Vec<(i32, u32)> {
     let today = Utc::now().naive_utc().date();
@@ -241,14 +395,80 @@ enum DownloadResult {
     Success,
     Skipped,
     NotFound,
+    /// 重试耗尽后仍传输失败 (网络/状态码错误)
+    Failed,
+    /// 重试耗尽后 SHA256 仍与 `.CHECKSUM` 不一致
+    Corrupt,
+}
+
+/// 一次 HTTP GET 的结果, 区分「确定不存在」和「可重试的瞬时失败」
+enum FetchOutcome {
+    Success(Vec<u8>),
+    NotFound,
     Failed,
 }
 
-/// 下载并解压单个文件
-async fn download_and_extract(client: &Client, task: DownloadTask) -> DownloadResult {
+async fn fetch_bytes(client: &Client, url: &str) -> FetchOutcome {
+    let response = match client.get(url).send().await {
+        Ok(r) => r,
+        Err(_) => return FetchOutcome::Failed,
+    };
+
+    if response.status().as_u16() == 404 {
+        return FetchOutcome::NotFound;
+    }
+    if !response.status().is_success() {
+        return FetchOutcome::Failed;
+    }
+
+    match response.bytes().await {
+        Ok(b) => FetchOutcome::Success(b.to_vec()),
+        Err(_) => FetchOutcome::Failed,
+    }
+}
+
+/// 拉取 data.binance.vision 为每个归档发布的 `<url>.CHECKSUM` 文件, 取出其中的
+/// SHA256 十六进制串。没有发布校验文件 (或拉取失败) 时返回 `None`, 调用方据此
+/// 跳过校验而不是当成错误。
+async fn fetch_expected_sha256(client: &Client, checksum_url: &str) -> Option<String> {
+    let response = client.get(checksum_url).send().await.ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+    let text = response.text().await.ok()?;
+    text.split_whitespace().next().map(|s| s.to_lowercase())
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    hasher.finalize().iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// 指数退避 + 抖动, 避免大量并发任务的重试在同一时刻撞在一起
+async fn backoff_sleep(backoff: &mut StdDuration) {
+    let jitter_ms = rand::thread_rng().gen_range(0..=(backoff.as_millis() as u64 / 2).max(1));
+    tokio::time::sleep(*backoff + StdDuration::from_millis(jitter_ms)).await;
+    *backoff = (*backoff * 2).min(RETRY_BACKOFF_CAP);
+}
+
+/// 下载并解压单个文件, 如配置了数据库则顺带把解析出的行 upsert 进去。
+/// 先用 sibling `.CHECKSUM` 文件校验 SHA256, 网络失败或校验不一致都会按
+/// `max_retries` 做指数退避重试; 重试耗尽后分别报告为 `Failed`/`Corrupt`。
+async fn download_and_extract(
+    client: &Client,
+    task: DownloadTask,
+    db: Option<DbPool>,
+    max_retries: u32,
+) -> DownloadResult {
     let output_path = task.output_path();
 
     if output_path.exists() {
+        if let Some(pool) = &db {
+            if ingest(pool, &task, &output_path).is_err() {
+                return DownloadResult::Failed;
+            }
+        }
         return DownloadResult::Skipped;
     }
 
@@ -258,22 +478,40 @@ async fn download_and_extract(client: &Client, task: DownloadTask) -> DownloadRe
         }
     }
 
-    let response = match client.get(&task.url()).send().await {
-        Ok(r) => r,
-        Err(_) => return DownloadResult::Failed,
-    };
-
-    if !response.status().is_success() {
-        return DownloadResult::NotFound;
-    }
+    let url = task.url();
+    let checksum_url = format!("{url}.CHECKSUM");
+    let mut backoff = RETRY_BACKOFF_INITIAL;
+
+    let zip_bytes = 'attempts: {
+        for attempt in 0..=max_retries {
+            let bytes = match fetch_bytes(client, &url).await {
+                FetchOutcome::Success(b) => b,
+                FetchOutcome::NotFound => return DownloadResult::NotFound,
+                FetchOutcome::Failed => {
+                    if attempt < max_retries {
+                        backoff_sleep(&mut backoff).await;
+                        continue;
+                    }
+                    return DownloadResult::Failed;
+                }
+            };
 
-    let bytes = match response.bytes().await {
-        Ok(b) => b,
-        Err(_) => return DownloadResult::Failed,
+            match fetch_expected_sha256(client, &checksum_url).await {
+                Some(expected) if sha256_hex(&bytes) != expected => {
+                    if attempt < max_retries {
+                        backoff_sleep(&mut backoff).await;
+                        continue;
+                    }
+                    return DownloadResult::Corrupt;
+                }
+                _ => break 'attempts bytes,
+            }
+        }
+        return DownloadResult::Failed;
     };
 
     let result = tokio::task::spawn_blocking(move || {
-        let cursor = Cursor::new(bytes);
+        let cursor = Cursor::new(zip_bytes);
         let mut archive = match zip::ZipArchive::new(cursor) {
             Ok(a) => a,
             Err(_) => return DownloadResult::Failed,
@@ -305,6 +543,12 @@ async fn download_and_extract(client: &Client, task: DownloadTask) -> DownloadRe
             }
         }
 
+        if let Some(pool) = &db {
+            if ingest(pool, &task, &output_path).is_err() {
+                return DownloadResult::Failed;
+            }
+        }
+
         DownloadResult::Success
     })
     .await;
@@ -318,6 +562,7 @@ struct Stats {
     skipped: AtomicU64,
     not_found: AtomicU64,
     failed: AtomicU64,
+    corrupt: AtomicU64,
 }
 
 impl Stats {
@@ -327,6 +572,7 @@ impl Stats {
             skipped: AtomicU64::new(0),
             not_found: AtomicU64::new(0),
             failed: AtomicU64::new(0),
+            corrupt: AtomicU64::new(0),
         }
     }
 
@@ -336,15 +582,17 @@ impl Stats {
             DownloadResult::Skipped => self.skipped.fetch_add(1, Ordering::Relaxed),
             DownloadResult::NotFound => self.not_found.fetch_add(1, Ordering::Relaxed),
             DownloadResult::Failed => self.failed.fetch_add(1, Ordering::Relaxed),
+            DownloadResult::Corrupt => self.corrupt.fetch_add(1, Ordering::Relaxed),
         };
     }
 
-    fn get_counts(&self) -> (u64, u64, u64, u64) {
+    fn get_counts(&self) -> (u64, u64, u64, u64, u64) {
         (
             self.success.load(Ordering::Relaxed),
             self.skipped.load(Ordering::Relaxed),
             self.not_found.load(Ordering::Relaxed),
             self.failed.load(Ordering::Relaxed),
+            self.corrupt.load(Ordering::Relaxed),
         )
     }
 }
@@ -355,6 +603,8 @@ async fn run_downloads(
     tasks: Vec<DownloadTask>,
     concurrent: usize,
     label: &str,
+    db: Option<DbPool>,
+    max_retries: u32,
 ) -> Result<(u64, u64, u64)> {
     let total = tasks.len();
     if total == 0 {
@@ -378,9 +628,10 @@ async fn run_downloads(
             let client = client.clone();
             let stats = stats.clone();
             let pb = pb.clone();
+            let db = db.clone();
 
             async move {
-                let result = download_and_extract(&client, task).await;
+                let result = download_and_extract(&client, task, db, max_retries).await;
                 stats.record(result);
                 pb.inc(1);
             }
@@ -391,13 +642,13 @@ async fn run_downloads(
 
     pb.finish();
 
-    let (success, skipped, not_found, failed) = stats.get_counts();
+    let (success, skipped, not_found, failed, corrupt) = stats.get_counts();
     println!(
-        "   ✅ 新下载: {} | ⏭️ 已存在: {} | 📭 不可用: {} | ❌ 失败: {}",
-        success, skipped, not_found, failed
+        "   ✅ 新下载: {} | ⏭️ 已存在: {} | 📭 不可用: {} | ❌ 失败: {} | 🧬 校验不通过: {}",
+        success, skipped, not_found, failed, corrupt
     );
 
-    Ok((success, skipped + not_found, failed))
+    Ok((success, skipped + not_found, failed + corrupt))
 }
 
 #[tokio::main]
@@ -414,13 +665,24 @@ async fn main() -> Result<()> {
 
     let output_dir = PathBuf::from(&args.output);
 
-    let futures_symbols = get_futures_symbols(&api_client, args.top).await?;
+    let futures_symbols = match args.source {
+        Source::BinanceVolume => get_futures_symbols(&api_client, args.top).await?,
+        Source::CoingeckoMcap => get_coingecko_symbols(&api_client, args.top).await?,
+    };
 
     if futures_symbols.is_empty() {
         println!("⚠️  没有找到合约");
         return Ok(());
     }
 
+    let db_pool = match &args.db {
+        Some(url) => {
+            println!("🗄️  数据库: {url}");
+            Some(db::open(url)?)
+        }
+        None => None,
+    };
+
     let mut total_success = 0u64;
     let mut total_skip = 0u64;
     let mut total_fail = 0u64;
@@ -466,12 +728,41 @@ async fn main() -> Result<()> {
                 kline_tasks,
                 args.concurrent,
                 "合约K线",
+                db_pool.clone(),
+                args.max_retries,
             )
             .await?;
             total_success += s;
             total_skip += sk;
             total_fail += f;
         }
+
+        // 月度归档永远不包含当前正在进行的月份, 用 REST 接口把缺口补上
+        if !args.no_resume {
+            println!("\n🔄 补齐K线缺口 (归档截止后到现在)...");
+            let mut filled = 0usize;
+            let mut errors = 0usize;
+            for symbol in &futures_symbols {
+                for interval in KLINE_INTERVALS {
+                    match resume::fill_recent_klines(
+                        &api_client,
+                        symbol,
+                        interval,
+                        &output_dir,
+                        args.max_retries,
+                    )
+                    .await
+                    {
+                        Ok(n) => filled += n,
+                        Err(e) => {
+                            errors += 1;
+                            eprintln!("   ⚠️  {symbol} {interval} 补齐失败: {e}");
+                        }
+                    }
+                }
+            }
+            println!("   ✅ 补齐 {filled} 条K线 | ❌ {errors} 个符号/周期失败");
+        }
     }
 
     // ========== 持仓量/Metrics (日度) ==========
@@ -503,12 +794,38 @@ async fn main() -> Result<()> {
                 metrics_tasks.len()
             );
 
-            let (s, sk, f) =
-                run_downloads(download_client.clone(), metrics_tasks, args.concurrent, "持仓量/Metrics").await?;
+            let (s, sk, f) = run_downloads(
+                download_client.clone(),
+                metrics_tasks,
+                args.concurrent,
+                "持仓量/Metrics",
+                db_pool.clone(),
+                args.max_retries,
+            )
+            .await?;
             total_success += s;
             total_skip += sk;
             total_fail += f;
         }
+
+        // 日度归档到昨天/前天为止, 剩下的用持仓量历史接口补齐
+        if !args.no_resume {
+            println!("\n🔄 补齐持仓量缺口 (归档截止后到现在)...");
+            let mut filled = 0usize;
+            let mut errors = 0usize;
+            for symbol in &futures_symbols {
+                match resume::fill_recent_metrics(&api_client, symbol, &output_dir, args.max_retries)
+                    .await
+                {
+                    Ok(n) => filled += n,
+                    Err(e) => {
+                        errors += 1;
+                        eprintln!("   ⚠️  {symbol} 持仓量补齐失败: {e}");
+                    }
+                }
+            }
+            println!("   ✅ 补齐 {filled} 条持仓量记录 | ❌ {errors} 个符号失败");
+        }
     }
 
     let elapsed = start_time.elapsed();
@@ -0,0 +1,263 @@
+use anyhow::{Context, Result};
+use chrono::{TimeZone, Utc};
+use rand::Rng;
+use reqwest::Client;
+use serde::Deserialize;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration as StdDuration;
+
+/// Monthly/daily archives only ever cover fully-elapsed periods (see
+/// `generate_months`/`generate_dates` in `main.rs`), so there's always a
+/// gap between the newest archived row and now. This module closes that
+/// gap over the live REST API instead of waiting for the next archive to
+/// publish: it reads the last `close_time`/`create_time` already on disk
+/// per symbol and only requests rows newer than that.
+const KLINE_PAGE_LIMIT: u32 = 1500;
+const OI_PAGE_LIMIT: u32 = 500;
+const OI_PERIOD: &str = "5m";
+const RETRY_BACKOFF_INITIAL: StdDuration = StdDuration::from_millis(500);
+const RETRY_BACKOFF_CAP: StdDuration = StdDuration::from_secs(20);
+
+/// Suffix our own output uses, so a re-run treats it as derived rather
+/// than folding it back in as part of the archive baseline.
+const RECENT_SUFFIX: &str = "recent.csv";
+
+#[derive(Debug, Deserialize)]
+struct RawKline(
+    i64,    // open_time
+    String, // open
+    String, // high
+    String, // low
+    String, // close
+    String, // volume
+    i64,    // close_time
+    String, // quote_volume
+    i64,    // count
+    String, // taker_buy_base
+    String, // taker_buy_quote
+    String, // ignore
+);
+
+#[derive(Debug, Deserialize)]
+struct OpenInterestHistEntry {
+    symbol: String,
+    #[serde(rename = "sumOpenInterest")]
+    sum_open_interest: String,
+    #[serde(rename = "sumOpenInterestValue")]
+    sum_open_interest_value: String,
+    timestamp: i64,
+}
+
+fn archived_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    Ok(fs::read_dir(dir)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| {
+            p.extension().map(|e| e == "csv").unwrap_or(false)
+                && p.file_name()
+                    .and_then(|n| n.to_str())
+                    .map(|n| !n.ends_with(RECENT_SUFFIX))
+                    .unwrap_or(false)
+        })
+        .collect())
+}
+
+fn last_nonempty_line(path: &Path) -> Result<Option<String>> {
+    let file = File::open(path).with_context(|| format!("无法打开: {}", path.display()))?;
+    Ok(BufReader::new(file)
+        .lines()
+        .filter_map(|l| l.ok())
+        .filter(|l| !l.trim().is_empty())
+        .last())
+}
+
+/// Last `close_time` (column 7 of the headerless kline CSV layout) across
+/// every archived file in `dir`, or `None` if nothing's archived yet (in
+/// which case the monthly backfill phase owns getting a baseline down,
+/// not this gap-filler).
+fn latest_archived_close_time(dir: &Path) -> Result<Option<i64>> {
+    let mut files = archived_files(dir)?;
+    files.sort();
+    let Some(last_file) = files.last() else {
+        return Ok(None);
+    };
+    let Some(line) = last_nonempty_line(last_file)? else {
+        return Ok(None);
+    };
+    Ok(line.split(',').nth(6).and_then(|s| s.trim().parse().ok()))
+}
+
+/// Same idea as [`latest_archived_close_time`] but for the metrics CSVs,
+/// whose first column is a `"%Y-%m-%d %H:%M:%S"` `create_time` string
+/// rather than an epoch millisecond column.
+fn latest_archived_metrics_time_ms(dir: &Path) -> Result<Option<i64>> {
+    let mut files = archived_files(dir)?;
+    files.sort();
+    let Some(last_file) = files.last() else {
+        return Ok(None);
+    };
+    let Some(line) = last_nonempty_line(last_file)? else {
+        return Ok(None);
+    };
+    let create_time = line.split(',').next().unwrap_or("");
+    Ok(
+        chrono::NaiveDateTime::parse_from_str(create_time, "%Y-%m-%d %H:%M:%S")
+            .ok()
+            .map(|dt| dt.and_utc().timestamp_millis()),
+    )
+}
+
+async fn fetch_json_with_retry<T>(client: &Client, url: &str, max_retries: u32) -> Result<T>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    let mut backoff = RETRY_BACKOFF_INITIAL;
+    for attempt in 0..=max_retries {
+        let response = client.get(url).send().await;
+        match response {
+            Ok(resp) if resp.status().is_success() => {
+                return resp.json::<T>().await.context("解析响应失败");
+            }
+            _ if attempt < max_retries => {
+                let jitter_ms =
+                    rand::thread_rng().gen_range(0..=(backoff.as_millis() as u64 / 2).max(1));
+                tokio::time::sleep(backoff + StdDuration::from_millis(jitter_ms)).await;
+                backoff = (backoff * 2).min(RETRY_BACKOFF_CAP);
+            }
+            Ok(resp) => anyhow::bail!("请求失败 (HTTP {}): {url}", resp.status()),
+            Err(e) => return Err(e).context(format!("请求失败: {url}")),
+        }
+    }
+    anyhow::bail!("请求失败: 重试耗尽: {url}")
+}
+
+fn kline_csv_line(k: &RawKline) -> String {
+    format!(
+        "{},{},{},{},{},{},{},{},{},{},{},{}",
+        k.0, k.1, k.2, k.3, k.4, k.5, k.6, k.7, k.8, k.9, k.10, k.11
+    )
+}
+
+/// Fetches every closed `interval` candle for `symbol` newer than the
+/// newest archived file's `close_time` and writes them to a fresh
+/// `<symbol>-<interval>-recent.csv` in the same `klines/<symbol>/<interval>`
+/// directory the monthly archives live in. Returns the number of rows
+/// written (0 if there's no archive baseline yet, or nothing newer).
+pub async fn fill_recent_klines(
+    client: &Client,
+    symbol: &str,
+    interval: &str,
+    output_dir: &Path,
+    max_retries: u32,
+) -> Result<usize> {
+    let dir = output_dir.join("klines").join(symbol).join(interval);
+    let Some(since) = latest_archived_close_time(&dir)? else {
+        return Ok(0);
+    };
+
+    let mut start_time = since + 1;
+    let mut rows = Vec::new();
+
+    loop {
+        let url = format!(
+            "https://fapi.binance.com/fapi/v1/klines?symbol={symbol}&interval={interval}&startTime={start_time}&limit={KLINE_PAGE_LIMIT}"
+        );
+        let batch: Vec<RawKline> = fetch_json_with_retry(client, &url, max_retries).await?;
+        let got = batch.len();
+        if got == 0 {
+            break;
+        }
+
+        start_time = batch.last().map(|k| k.6 + 1).unwrap_or(start_time);
+        let now_ms = Utc::now().timestamp_millis();
+        rows.extend(batch.iter().filter(|k| k.6 < now_ms).map(kline_csv_line));
+
+        if got < KLINE_PAGE_LIMIT as usize {
+            break;
+        }
+    }
+
+    if rows.is_empty() {
+        return Ok(0);
+    }
+
+    fs::create_dir_all(&dir)?;
+    let mut file = File::create(dir.join(format!("{symbol}-{interval}-{RECENT_SUFFIX}")))?;
+    for row in &rows {
+        writeln!(file, "{row}")?;
+    }
+
+    Ok(rows.len())
+}
+
+/// Same idea as [`fill_recent_klines`] for the open-interest side: fetches
+/// 5-minute open-interest samples newer than the newest archived metrics
+/// file. Only `sum_open_interest`/`sum_open_interest_value` come from this
+/// endpoint; the long/short-ratio columns aren't available from a single
+/// REST call the way the daily archive bundles them, so they're written
+/// as `0` (FastScanner/MetricsLoader only ever read `sum_open_interest`).
+pub async fn fill_recent_metrics(
+    client: &Client,
+    symbol: &str,
+    output_dir: &Path,
+    max_retries: u32,
+) -> Result<usize> {
+    let dir = output_dir.join("metrics").join(symbol);
+    let Some(since) = latest_archived_metrics_time_ms(&dir)? else {
+        return Ok(0);
+    };
+
+    let mut start_time = since + 1;
+    let mut rows = Vec::new();
+
+    loop {
+        let url = format!(
+            "https://fapi.binance.com/futures/data/openInterestHist?symbol={symbol}&period={OI_PERIOD}&startTime={start_time}&limit={OI_PAGE_LIMIT}"
+        );
+        let batch: Vec<OpenInterestHistEntry> =
+            fetch_json_with_retry(client, &url, max_retries).await?;
+        let got = batch.len();
+        if got == 0 {
+            break;
+        }
+
+        start_time = batch.last().map(|e| e.timestamp + 1).unwrap_or(start_time);
+
+        for entry in &batch {
+            let create_time = Utc
+                .timestamp_millis_opt(entry.timestamp)
+                .single()
+                .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+                .unwrap_or_default();
+            rows.push(format!(
+                "{create_time},{},{},{},0,0,0,0",
+                entry.symbol, entry.sum_open_interest, entry.sum_open_interest_value
+            ));
+        }
+
+        if got < OI_PAGE_LIMIT as usize {
+            break;
+        }
+    }
+
+    if rows.is_empty() {
+        return Ok(0);
+    }
+
+    fs::create_dir_all(&dir)?;
+    let mut file = File::create(dir.join(format!("{symbol}-metrics-{RECENT_SUFFIX}")))?;
+    writeln!(
+        file,
+        "create_time,symbol,sum_open_interest,sum_open_interest_value,count_toptrader_long_short_ratio,sum_toptrader_long_short_ratio,count_long_short_ratio,sum_taker_long_short_vol_ratio"
+    )?;
+    for row in &rows {
+        writeln!(file, "{row}")?;
+    }
+
+    Ok(rows.len())
+}